@@ -3,7 +3,7 @@
 use crate::smart_num::*;
 use std::{error::Error, fmt::Display};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Variable {
     pub name: String,
 }
@@ -22,7 +22,7 @@ impl Display for Variable {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AstOperand {
     Num(SmartNum),
     Variable(Variable),
@@ -1,8 +1,8 @@
 #![allow(dead_code)]
 
-use std::fmt::Display;
+use std::{fmt::Display, hash::Hash};
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum OperatorType {
     Neg,
     Add,
@@ -14,6 +14,53 @@ pub enum OperatorType {
     Exp,
     Ln,
     Pow,
+    /// n! of a non-negative integer n.
+    Factorial,
+    /// Greatest integer not above the argument.
+    Floor,
+    /// Applied undefined function of arbitrary arity, e.g. f(x, y).
+    Function(String),
+    /// Unevaluated partial derivative of an undefined function, applied
+    /// like the function itself. Holds the name and the sorted 0-based
+    /// indices of the arguments it is taken in, e.g. f_12(x, y) is
+    /// FunctionDerivative("f", [0, 1]).
+    FunctionDerivative(String, Vec<usize>),
+    /// Unevaluated derivative, children are [body, variable].
+    Derivative,
+    /// Unevaluated integral, children are [body, variable] or
+    /// [body, variable, lower, upper]. The variable is bound in body.
+    Integral,
+    /// Unevaluated limit, children are [body, variable, point] or
+    /// [body, variable, point, side]. The variable is bound in body.
+    Limit,
+    /// Unevaluated summation, children are [body, variable, lower, upper].
+    /// The variable is bound in body.
+    Sum,
+}
+
+impl OperatorType {
+    /// Whether the second child is a dummy variable bound in the first one.
+    pub(crate) fn binds_variable(&self) -> bool {
+        matches!(
+            self,
+            OperatorType::Integral | OperatorType::Limit | OperatorType::Sum
+        )
+    }
+
+    /// Whether the node is printed in call notation, e.g. f(x, y).
+    pub(crate) fn is_call_like(&self) -> bool {
+        matches!(
+            self,
+            OperatorType::Factorial
+                | OperatorType::Floor
+                | OperatorType::Function(_)
+                | OperatorType::FunctionDerivative(..)
+                | OperatorType::Derivative
+                | OperatorType::Integral
+                | OperatorType::Limit
+                | OperatorType::Sum
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +78,12 @@ impl PartialEq for AstOperator {
 
 impl Eq for AstOperator {}
 
+impl Hash for AstOperator {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.descriptor.hash(state);
+    }
+}
+
 impl Display for AstOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.symbol)
@@ -2,7 +2,7 @@
 
 use super::op::{
     operand::{AstOperand, Variable},
-    operator::AstOperator,
+    operator::{AstOperator, OperatorType},
 };
 use crate::smart_num::{
     rational::{RationalNum, ToRational},
@@ -10,7 +10,7 @@ use crate::smart_num::{
 };
 use std::{error::Error, fmt::Display, vec};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AstNode {
     Operator(AstOperator),
     Operand(AstOperand),
@@ -25,7 +25,9 @@ where
     }
 }
 
-#[derive(Debug, Clone)]
+/// Equality and hashing are structural, so expressions can be used
+/// as keys of memoization tables.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Expression {
     pub root: AstNode,
     pub child: Vec<Expression>,
@@ -80,7 +82,10 @@ impl Expression {
         match &self.root {
             AstNode::Operand(v) => v.to_string(),
             AstNode::Operator(op) => {
-                let me = if self.child.len() == 2 {
+                let me = if op.descriptor.is_call_like() {
+                    let args: Vec<String> = self.child.iter().map(|c| c.to_string()).collect();
+                    format!("{}({})", op, args.join(", "))
+                } else if self.child.len() == 2 {
                     // binary operator
                     format!(
                         "{} {} {}",
@@ -151,6 +156,65 @@ impl Expression {
     pub fn e() -> Expression {
         Expression::from(SmartNum::e())
     }
+
+    /// Variables appearing in the expression, in order of first occurrence.
+    pub fn free_variables(&self) -> Vec<Variable> {
+        let mut variables: Vec<Variable> = vec![];
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<Variable>) {
+        match &self.root {
+            AstNode::Operand(AstOperand::Variable(v)) => {
+                if !variables.iter().any(|u| u.name == v.name) {
+                    variables.push(v.clone());
+                }
+            }
+            AstNode::Operand(_) => {}
+            AstNode::Operator(op) if op.descriptor.binds_variable() => {
+                // the dummy variable is only visible inside the body
+                let bound = self.bound_variable();
+                for v in self.child[0].free_variables() {
+                    if Some(&v) != bound && !variables.iter().any(|u| u.name == v.name) {
+                        variables.push(v);
+                    }
+                }
+                for c in &self.child[2..] {
+                    c.collect_variables(variables);
+                }
+            }
+            AstNode::Operator(_) => {
+                for c in &self.child {
+                    c.collect_variables(variables);
+                }
+            }
+        }
+    }
+
+    pub fn contains_variable(&self, of: &Variable) -> bool {
+        match &self.root {
+            AstNode::Operand(AstOperand::Variable(v)) => v.name == of.name,
+            AstNode::Operand(_) => false,
+            AstNode::Operator(op) if op.descriptor.binds_variable() => {
+                (self.bound_variable() != Some(of) && self.child[0].contains_variable(of))
+                    || self.child[2..].iter().any(|c| c.contains_variable(of))
+            }
+            AstNode::Operator(_) => self.child.iter().any(|c| c.contains_variable(of)),
+        }
+    }
+
+    /// The variable of an unevaluated derivative, integral, limit or sum.
+    pub fn bound_variable(&self) -> Option<&Variable> {
+        match &self.root {
+            AstNode::Operator(op)
+                if op.descriptor == OperatorType::Derivative || op.descriptor.binds_variable() =>
+            {
+                (&self.child[1]).into()
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Display for Expression {
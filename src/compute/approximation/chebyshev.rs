@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use std::f64::consts::PI;
+
+use crate::ast::{op::operand::Variable, tree::Expression};
+
+use super::{
+    add_scaled, check_interval, from_unit_interval, max_error, mul_linear, polynomial, sampler,
+    ApproximationError, PolynomialApproximation,
+};
+
+/// The n Chebyshev nodes of the first kind on [-1, 1], the roots of T_n.
+pub(crate) fn chebyshev_nodes(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|k| (PI * (k as f64 + 0.5) / n as f64).cos())
+        .collect()
+}
+
+/// Coefficients in t of T_0, ..., T_**n**.
+pub(crate) fn chebyshev_polynomials(n: usize) -> Vec<Vec<f64>> {
+    let mut t = vec![vec![1.0], vec![0.0, 1.0]];
+    while t.len() <= n {
+        let k = t.len();
+        let next = add_scaled(&mul_linear(&t[k - 1], 2.0, 0.0), -1.0, &t[k - 2]);
+        t.push(next);
+    }
+    t.truncate(n + 1);
+    t
+}
+
+/// Interpolant of **expr** in **var** of degree **degree** through the
+/// Chebyshev nodes of [**a**, **b**], within a small factor of the best
+/// polynomial of that degree.
+pub fn chebyshev_approximation(
+    expr: &Expression,
+    var: &Variable,
+    a: f64,
+    b: f64,
+    degree: usize,
+) -> Result<PolynomialApproximation, ApproximationError> {
+    check_interval(expr, a, b)?;
+    let f = sampler(expr, var);
+    let n = degree + 1;
+    let nodes = chebyshev_nodes(n);
+    let values = nodes
+        .iter()
+        .map(|t| f((a + b) / 2.0 + (b - a) / 2.0 * t))
+        .collect::<Result<Vec<f64>, ApproximationError>>()?;
+    // discrete orthogonality of T_j over the nodes
+    let t = chebyshev_polynomials(degree);
+    let mut unit = vec![];
+    for (j, tj) in t.iter().enumerate() {
+        let mut c = 2.0 / n as f64
+            * nodes
+                .iter()
+                .zip(&values)
+                .map(|(x, v)| v * (j as f64 * x.acos()).cos())
+                .sum::<f64>();
+        if j == 0 {
+            c /= 2.0;
+        }
+        unit = add_scaled(&unit, c, tj);
+    }
+    let coefficient = from_unit_interval(&unit, a, b);
+    Ok(PolynomialApproximation {
+        max_error: max_error(&f, &coefficient, a, b, &[])?,
+        polynomial: polynomial(&coefficient, var),
+        coefficient,
+    })
+}
+
+#[cfg(test)]
+mod chebyshev_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{exp::exp, pow::Pow},
+    };
+
+    use super::chebyshev_approximation;
+
+    #[test]
+    fn interpolation() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let p = chebyshev_approximation(&exp(x.clone()), &vx, 0.0, 1.0, 3).unwrap();
+        assert!(p.max_error < 1e-3);
+        assert_eq!(p.coefficient.len(), 4);
+        // polynomials of the degree are reproduced
+        let f = Expression::from(3) * x.clone().pow(2) - x.clone();
+        let p = chebyshev_approximation(&f, &vx, -1.0, 2.0, 2).unwrap();
+        assert!(p.max_error < 1e-12);
+        assert!((p.coefficient[2] - 3.0).abs() < 1e-12);
+        assert!((p.coefficient[1] + 1.0).abs() < 1e-12);
+        assert!(chebyshev_approximation(&f, &vx, 1.0, 1.0, 2).is_err());
+    }
+}
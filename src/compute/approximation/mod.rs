@@ -0,0 +1,140 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    math_op::pow::Pow,
+};
+
+use super::evaluate::Evaluate;
+
+pub mod chebyshev;
+pub mod remez;
+
+/// Points the error is sampled at, on top of the extrema found by Remez.
+const ERROR_SAMPLES: usize = 2000;
+
+/// Polynomial standing in for a function on [**a**, **b**].
+#[derive(Debug, Clone)]
+pub struct PolynomialApproximation {
+    /// Coefficient of x^k, x being the variable of the function.
+    pub coefficient: Vec<f64>,
+    pub polynomial: Expression,
+    /// Largest |f - p| found on the interval.
+    pub max_error: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApproximationError {
+    err_expr: Expression,
+    reason: &'static str,
+}
+
+impl Display for ApproximationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cannot approximate {} due to {}",
+            self.err_expr, self.reason
+        )
+    }
+}
+
+/// **expr** as a function of **var** that fails on non-finite values.
+pub(crate) fn sampler<'a>(
+    expr: &'a Expression,
+    var: &'a Variable,
+) -> impl Fn(f64) -> Result<f64, ApproximationError> + 'a {
+    move |x| {
+        expr.evaluate((var, x)).map_err(|_| ApproximationError {
+            err_expr: expr.clone(),
+            reason: "a point of the interval out of its domain",
+        })
+    }
+}
+
+pub(crate) fn check_interval(expr: &Expression, a: f64, b: f64) -> Result<(), ApproximationError> {
+    if a.is_finite() && b.is_finite() && a < b {
+        Ok(())
+    } else {
+        Err(ApproximationError {
+            err_expr: expr.clone(),
+            reason: "an empty or unbounded interval",
+        })
+    }
+}
+
+/// Value at **x** of the polynomial with coefficients **c**, by Horner.
+pub(crate) fn horner(c: &[f64], x: f64) -> f64 {
+    c.iter().rev().fold(0.0, |s, c| s * x + c)
+}
+
+/// Coefficients of **p** * (**alpha** x + **beta**).
+pub(crate) fn mul_linear(p: &[f64], alpha: f64, beta: f64) -> Vec<f64> {
+    let mut r = vec![0.0; p.len() + 1];
+    for (k, c) in p.iter().enumerate() {
+        r[k] += beta * c;
+        r[k + 1] += alpha * c;
+    }
+    r
+}
+
+/// **p** + **s** * **q**.
+pub(crate) fn add_scaled(p: &[f64], s: f64, q: &[f64]) -> Vec<f64> {
+    let mut r = vec![0.0; p.len().max(q.len())];
+    for (k, c) in p.iter().enumerate() {
+        r[k] += c;
+    }
+    for (k, c) in q.iter().enumerate() {
+        r[k] += s * c;
+    }
+    r
+}
+
+/// Coefficients in x of sum of **c[k]** t^k with t = (2 x - a - b) / (b - a).
+pub(crate) fn from_unit_interval(c: &[f64], a: f64, b: f64) -> Vec<f64> {
+    let (alpha, beta) = (2.0 / (b - a), -(a + b) / (b - a));
+    c.iter().rev().fold(vec![], |s, c| {
+        let s = mul_linear(&s, alpha, beta);
+        add_scaled(&s, 1.0, &[*c])
+    })
+}
+
+/// Largest |f - p| over evenly spaced points and **extra** ones.
+pub(crate) fn max_error<F>(
+    f: &F,
+    c: &[f64],
+    a: f64,
+    b: f64,
+    extra: &[f64],
+) -> Result<f64, ApproximationError>
+where
+    F: Fn(f64) -> Result<f64, ApproximationError>,
+{
+    let grid = (0..=ERROR_SAMPLES).map(|i| a + (b - a) * i as f64 / ERROR_SAMPLES as f64);
+    grid.chain(extra.iter().copied())
+        .map(|x| Ok((f(x)? - horner(c, x)).abs()))
+        .try_fold(0.0, |m: f64, e: Result<f64, ApproximationError>| {
+            Ok(m.max(e?))
+        })
+}
+
+/// The polynomial in **var** with coefficients **c**, leaving out the
+/// vanishing terms.
+pub(crate) fn polynomial(c: &[f64], var: &Variable) -> Expression {
+    let x = Expression::from(var.clone());
+    c.iter()
+        .enumerate()
+        .filter(|(_, c)| **c != 0.0)
+        .map(|(k, c)| {
+            let c = Expression::from(*c);
+            match k {
+                0 => c,
+                1 => c * x.clone(),
+                _ => c * x.clone().pow(k as i64),
+            }
+        })
+        .reduce(|a, b| a + b)
+        .unwrap_or(Expression::zero())
+}
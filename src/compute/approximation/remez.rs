@@ -0,0 +1,175 @@
+#![allow(dead_code)]
+
+use std::f64::consts::PI;
+
+use crate::ast::{op::operand::Variable, tree::Expression};
+
+use super::{
+    add_scaled, chebyshev::chebyshev_polynomials, check_interval, from_unit_interval, horner,
+    max_error, polynomial, sampler, ApproximationError, PolynomialApproximation,
+};
+
+/*
+ * Remez exchange on t = (2 x - a - b) / (b - a) in [-1, 1]. With a
+ * reference of degree + 2 points, p and the levelled error E solve
+ *   p(t_i) + (-1)^i E = f(t_i),
+ * then the reference moves to the extrema of f - p, one per stretch where
+ * the error keeps its sign, until all of them have the same size. The
+ * polynomial is kept in the Chebyshev basis while solving for conditioning.
+*/
+
+/// Exchanges tried before settling for the current reference.
+const MAX_EXCHANGES: usize = 40;
+
+/// Points the error is scanned at for its extrema.
+const SCAN_POINTS: usize = 2000;
+
+/// Relative spread of the extrema under which the error counts as levelled.
+const LEVELLED: f64 = 1e-9;
+
+/// Solves **a** x = **b** by Gaussian elimination with partial pivoting.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for r in col + 1..n {
+            let f = a[r][col] / a[col][col];
+            let pivot_row = a[col].clone();
+            for (x, p) in a[r].iter_mut().zip(pivot_row).skip(col) {
+                *x -= f * p;
+            }
+            b[r] -= f * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let s: f64 = (i + 1..n).map(|j| a[i][j] * x[j]).sum();
+        x[i] = (b[i] - s) / a[i][i];
+    }
+    Some(x)
+}
+
+/// Extrema of **e** over a scan of [-1, 1], one per stretch of constant
+/// sign, so that consecutive ones alternate.
+fn alternating_extrema(e: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut extrema: Vec<(f64, f64)> = vec![];
+    for &(t, v) in e {
+        match extrema.last_mut() {
+            Some(last) if (last.1 >= 0.0) == (v >= 0.0) => {
+                if v.abs() > last.1.abs() {
+                    *last = (t, v);
+                }
+            }
+            _ => extrema.push((t, v)),
+        }
+    }
+    extrema
+}
+
+/// Polynomial of degree **degree** in **var** minimising the largest
+/// error to **expr** on [**a**, **b**], starting from the extrema of the
+/// Chebyshev polynomial of the next degree.
+pub fn minimax_approximation(
+    expr: &Expression,
+    var: &Variable,
+    a: f64,
+    b: f64,
+    degree: usize,
+) -> Result<PolynomialApproximation, ApproximationError> {
+    check_interval(expr, a, b)?;
+    let f = sampler(expr, var);
+    let g = |t: f64| f((a + b) / 2.0 + (b - a) / 2.0 * t);
+    let n = degree + 2;
+    let mut reference: Vec<f64> = (0..n)
+        .map(|i| -(PI * i as f64 / (n - 1) as f64).cos())
+        .collect();
+    let basis = chebyshev_polynomials(degree);
+    let mut unit = vec![];
+    for _ in 0..MAX_EXCHANGES {
+        let rows = reference
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let mut row: Vec<f64> = basis.iter().map(|tk| horner(tk, *t)).collect();
+                row.push(if i % 2 == 0 { 1.0 } else { -1.0 });
+                row
+            })
+            .collect();
+        let values = reference
+            .iter()
+            .map(|t| g(*t))
+            .collect::<Result<Vec<f64>, ApproximationError>>()?;
+        let solution = solve(rows, values).ok_or(ApproximationError {
+            err_expr: expr.clone(),
+            reason: "a singular reference",
+        })?;
+        unit = basis
+            .iter()
+            .zip(&solution)
+            .fold(vec![], |p, (tk, c)| add_scaled(&p, *c, tk));
+        let scan = (0..=SCAN_POINTS)
+            .map(|i| {
+                let t = -1.0 + 2.0 * i as f64 / SCAN_POINTS as f64;
+                Ok((t, g(t)? - horner(&unit, t)))
+            })
+            .collect::<Result<Vec<(f64, f64)>, ApproximationError>>()?;
+        let mut extrema = alternating_extrema(&scan);
+        if extrema.len() < n {
+            break;
+        }
+        while extrema.len() > n {
+            if extrema[0].1.abs() < extrema[extrema.len() - 1].1.abs() {
+                extrema.remove(0);
+            } else {
+                extrema.pop();
+            }
+        }
+        let sizes = extrema.iter().map(|(_, e)| e.abs());
+        let (low, high) = sizes.fold((f64::INFINITY, 0.0_f64), |(l, h), e| (l.min(e), h.max(e)));
+        reference = extrema.into_iter().map(|(t, _)| t).collect();
+        if high - low <= LEVELLED * high {
+            break;
+        }
+    }
+    let coefficient = from_unit_interval(&unit, a, b);
+    let points: Vec<f64> = reference
+        .iter()
+        .map(|t| (a + b) / 2.0 + (b - a) / 2.0 * t)
+        .collect();
+    Ok(PolynomialApproximation {
+        max_error: max_error(&f, &coefficient, a, b, &points)?,
+        polynomial: polynomial(&coefficient, var),
+        coefficient,
+    })
+}
+
+#[cfg(test)]
+mod remez_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::approximation::chebyshev::chebyshev_approximation,
+        math_op::exp::exp,
+    };
+
+    use super::minimax_approximation;
+
+    #[test]
+    fn minimax() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let f = exp(x.clone());
+        // the best line on [0, 1] is off by 1 - (e - (e - 1) ln(e - 1)) / 2
+        let line = minimax_approximation(&f, &vx, 0.0, 1.0, 1).unwrap();
+        assert!((line.max_error - 0.105933).abs() < 1e-5);
+        assert!((line.coefficient[1] - (1_f64.exp() - 1.0)).abs() < 1e-6);
+        let cubic = minimax_approximation(&f, &vx, 0.0, 1.0, 3).unwrap();
+        let interpolant = chebyshev_approximation(&f, &vx, 0.0, 1.0, 3).unwrap();
+        assert!(cubic.max_error < interpolant.max_error);
+        assert!(cubic.max_error < 5.5e-4);
+    }
+}
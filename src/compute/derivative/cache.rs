@@ -0,0 +1,163 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::ast::{op::operand::Variable, tree::Expression};
+
+use super::{derivative_with, DerivativeError};
+
+/// Memoization table of derivatives keyed by the structure of the
+/// differentiated expression. Every subtree is looked up before being
+/// differentiated, so repeated and nested requests share their work.
+#[derive(Debug, Clone, Default)]
+pub struct DerivativeCache {
+    memo: HashMap<(Expression, Variable), Expression>,
+}
+
+impl DerivativeCache {
+    pub fn new() -> DerivativeCache {
+        DerivativeCache {
+            memo: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.memo.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.memo.is_empty()
+    }
+
+    pub fn derivative(
+        &mut self,
+        expr: &Expression,
+        of: &Variable,
+    ) -> Result<Expression, DerivativeError<Expression>> {
+        let key = (expr.clone(), of.clone());
+        if let Some(d) = self.memo.get(&key) {
+            return Ok(d.clone());
+        }
+        let d = derivative_with(expr.clone(), of, &mut |sub| self.derivative(&sub, of))?;
+        self.memo.insert(key, d.clone());
+        Ok(d)
+    }
+
+    pub fn nth_derivative(
+        &mut self,
+        expr: &Expression,
+        of: &Variable,
+        n: u64,
+    ) -> Result<Expression, DerivativeError<Expression>> {
+        let mut d = expr.clone();
+        for _ in 0..n {
+            d = self.derivative(&d, of)?;
+        }
+        Ok(d)
+    }
+
+    /// Mixed partial derivative, **index** lists each variable with its
+    /// order. Variables are differentiated in name order so that requests
+    /// differing only in ordering hit the same entries.
+    pub fn partial_derivative(
+        &mut self,
+        expr: &Expression,
+        index: &[(&Variable, u64)],
+    ) -> Result<Expression, DerivativeError<Expression>> {
+        let mut orders: Vec<(&Variable, u64)> = vec![];
+        for (v, n) in index {
+            match orders.iter_mut().find(|(u, _)| u.name == v.name) {
+                Some((_, m)) => *m += n,
+                None => orders.push((v, *n)),
+            }
+        }
+        orders.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+        let mut d = expr.clone();
+        for (v, n) in orders {
+            d = self.nth_derivative(&d, v, n)?;
+        }
+        Ok(d)
+    }
+}
+
+pub trait HigherDerivative<T> {
+    type Output;
+    fn nth_derivative(self, of: T, n: u64) -> Self::Output;
+    fn partial_derivative(self, index: &[(T, u64)]) -> Self::Output;
+}
+
+impl<'a> HigherDerivative<&'a Variable> for Expression {
+    type Output = Result<Expression, DerivativeError<Expression>>;
+
+    fn nth_derivative(self, of: &'a Variable, n: u64) -> Self::Output {
+        DerivativeCache::new().nth_derivative(&self, of, n)
+    }
+
+    fn partial_derivative(self, index: &[(&'a Variable, u64)]) -> Self::Output {
+        DerivativeCache::new().partial_derivative(&self, index)
+    }
+}
+
+#[cfg(test)]
+mod derivative_cache_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::derivative::Derivative,
+        math_op::{exp::exp, pow::Pow, sin::sin},
+    };
+
+    use super::{DerivativeCache, HigherDerivative};
+
+    #[test]
+    fn nth_derivative() {
+        let x = Expression::new_variable("x");
+        let of = Variable::new_variable("x");
+        let y = sin(x.clone());
+        assert_eq!(
+            y.clone().nth_derivative(&of, 0).unwrap().to_string(),
+            "sinx"
+        );
+        assert_eq!(
+            y.clone().nth_derivative(&of, 2).unwrap().to_string(),
+            "-sinx"
+        );
+        let expected = y
+            .clone()
+            .derivative(&of)
+            .unwrap()
+            .derivative(&of)
+            .unwrap()
+            .derivative(&of)
+            .unwrap();
+        assert_eq!(y.nth_derivative(&of, 3).unwrap(), expected);
+    }
+
+    #[test]
+    fn mixed_derivative() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let (vx, vy) = (Variable::new_variable("x"), Variable::new_variable("y"));
+        let f = x.clone().pow(3) * y.clone().pow(2);
+        let mut cache = DerivativeCache::new();
+        let a = cache.partial_derivative(&f, &[(&vx, 2), (&vy, 1)]).unwrap();
+        let hits = cache.len();
+        let b = cache
+            .partial_derivative(&f, &[(&vy, 1), (&vx, 1), (&vx, 1)])
+            .unwrap();
+        assert_eq!(a, b);
+        assert_eq!(cache.len(), hits);
+    }
+
+    #[test]
+    fn shared_subtrees() {
+        let x = Expression::new_variable("x");
+        let of = Variable::new_variable("x");
+        let u = exp(sin(x.clone()));
+        let mut cache = DerivativeCache::new();
+        cache.derivative(&u, &of).unwrap();
+        let entries = cache.len();
+        // u appears twice, its derivative is only computed once
+        cache.derivative(&(u.clone() * u), &of).unwrap();
+        assert_eq!(cache.len(), entries + 1);
+    }
+}
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::num_aggregate::NumAggregate,
+};
+
+use super::{cache::DerivativeCache, DerivativeError};
+
+/// d^n y / dx^n for the curve **f**(x, y) = 0.
+///
+/// The first derivative is -F_x / F_y by the implicit function theorem.
+/// Every further order differentiates the previous result totally in x,
+/// i.e. G' = G_x + G_y * y'.
+pub fn implicit_derivative(
+    f: &Expression,
+    y: &Variable,
+    x: &Variable,
+    order: u64,
+) -> Result<Expression, DerivativeError<Expression>> {
+    if order == 0 {
+        return Ok(Expression::from(y.clone()));
+    }
+    let mut cache = DerivativeCache::new();
+    let f_x = cache.derivative(f, x)?;
+    let f_y = cache.derivative(f, y)?;
+    let y_d = (-f_x / f_y).num_aggregate();
+    let mut g = y_d.clone();
+    for _ in 1..order {
+        let g_x = cache.derivative(&g, x)?;
+        let g_y = cache.derivative(&g, y)?;
+        g = (g_x + g_y * y_d.clone()).num_aggregate();
+    }
+    Ok(g)
+}
+
+#[cfg(test)]
+mod implicit_tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::evaluate::Evaluate,
+        math_op::{exp::exp, pow::Pow},
+    };
+
+    use super::implicit_derivative;
+
+    fn at(expr: &Expression, x: f64, y: f64) -> f64 {
+        let mut bindings = HashMap::new();
+        bindings.insert("x".to_string(), x);
+        bindings.insert("y".to_string(), y);
+        expr.evaluate(&bindings).unwrap()
+    }
+
+    #[test]
+    fn circle() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let (vx, vy) = (Variable::new_variable("x"), Variable::new_variable("y"));
+        let f = x.clone().pow(2) + y.clone().pow(2) - Expression::one();
+        let d1 = implicit_derivative(&f, &vy, &vx, 1).unwrap();
+        let d2 = implicit_derivative(&f, &vy, &vx, 2).unwrap();
+        assert!((at(&d1, 0.6, 0.8) + 0.75).abs() < 1e-9);
+        // y'' = -1 / y^3 on the unit circle
+        assert!((at(&d2, 0.6, 0.8) + 1.0 / 0.512).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exponential_curve() {
+        // y - exp(x) = 0 has every derivative equal to exp(x) = y
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let (vx, vy) = (Variable::new_variable("x"), Variable::new_variable("y"));
+        let f = y - exp(x);
+        for order in 1..4 {
+            let d = implicit_derivative(&f, &vy, &vx, order).unwrap();
+            assert!((at(&d, 0.3, 0.3_f64.exp()) - 0.3_f64.exp()).abs() < 1e-9);
+        }
+    }
+}
@@ -1,4 +1,8 @@
+pub mod cache;
+pub mod implicit;
+pub mod multivariate;
 mod rules;
+pub mod total;
 
 use std::fmt::Display;
 
@@ -11,10 +15,22 @@ use crate::ast::{
 };
 
 use self::rules::{
-    add::add_derivative_rule, cos::cos_derivative_rule, div::div_derivative_rule,
-    exp::exp_derivative_rule, ln::ln_derivative_rule, mul::mul_derivative_rule,
-    neg::neg_derivative_rule, pow::pow_derivative_rule, sin::sin_derivative_rule,
+    add::add_derivative_rule,
+    cos::cos_derivative_rule,
+    discrete::{factorial_derivative_rule, floor_derivative_rule},
+    div::div_derivative_rule,
+    exp::exp_derivative_rule,
+    function::function_derivative_rule,
+    ln::ln_derivative_rule,
+    mul::mul_derivative_rule,
+    neg::neg_derivative_rule,
+    pow::pow_derivative_rule,
+    sin::sin_derivative_rule,
     sub::sub_derivative_rule,
+    unevaluated::{
+        derivative_node_derivative_rule, integral_derivative_rule, limit_node_derivative_rule,
+        sum_derivative_rule,
+    },
 };
 
 use super::num_aggregate::NumAggregate;
@@ -38,6 +54,57 @@ where
     }
 }
 
+/// Differentiate the root node of **expr**, children are
+/// differentiated through **d** so that callers can memoize them.
+pub(crate) fn derivative_with<D>(
+    expr: Expression,
+    to: &Variable,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let result = match expr.root {
+        AstNode::Operand(operand) => match operand {
+            AstOperand::Num(_) => Expression::from(0_i64),
+            AstOperand::Variable(another) => {
+                if to.name == another.name {
+                    Expression::from(1_i64)
+                } else {
+                    Expression::from(0_i64)
+                }
+            }
+        },
+        AstNode::Operator(operator) => {
+            let child = expr.child;
+            match operator.descriptor {
+                OperatorType::Neg => neg_derivative_rule(child, d)?,
+                OperatorType::Add => add_derivative_rule(child, d)?,
+                OperatorType::Sub => sub_derivative_rule(child, d)?,
+                OperatorType::Mul => mul_derivative_rule(child, d)?,
+                OperatorType::Div => div_derivative_rule(child, d)?,
+                OperatorType::Sin => sin_derivative_rule(child, d)?,
+                OperatorType::Cos => cos_derivative_rule(child, d)?,
+                OperatorType::Exp => exp_derivative_rule(child, d)?,
+                OperatorType::Ln => ln_derivative_rule(child, d)?,
+                OperatorType::Pow => pow_derivative_rule(child, d)?,
+                OperatorType::Factorial => factorial_derivative_rule(child, d)?,
+                OperatorType::Floor => floor_derivative_rule(child, d)?,
+                OperatorType::Function(name) => function_derivative_rule(name, vec![], child, d)?,
+                OperatorType::FunctionDerivative(name, wrt) => {
+                    function_derivative_rule(name, wrt, child, d)?
+                }
+                OperatorType::Derivative => derivative_node_derivative_rule(child, d)?,
+                OperatorType::Integral => integral_derivative_rule(child, to, d)?,
+                OperatorType::Limit => limit_node_derivative_rule(child, d)?,
+                OperatorType::Sum => sum_derivative_rule(child, to, d)?,
+            }
+        }
+    }
+    .num_aggregate();
+    Ok(result)
+}
+
 impl<'a> Derivative<'a, &Variable> for Expression {
     type Output = Result<Expression, DerivativeError<Expression>>;
 
@@ -45,35 +112,7 @@ impl<'a> Derivative<'a, &Variable> for Expression {
         let to = Into::<Option<&Variable>>::into(to_).ok_or(DerivativeError {
             err_src: Expression::from(to_.clone()),
         })?;
-        let result = match self.root {
-            AstNode::Operand(operand) => match operand {
-                AstOperand::Num(_) => Expression::from(0_i64),
-                AstOperand::Variable(another) => {
-                    if to.name == another.name {
-                        Expression::from(1_i64)
-                    } else {
-                        Expression::from(0_i64)
-                    }
-                }
-            },
-            AstNode::Operator(operator) => {
-                let child = self.child;
-                match operator.descriptor {
-                    OperatorType::Neg => neg_derivative_rule(child, to)?,
-                    OperatorType::Add => add_derivative_rule(child, to)?,
-                    OperatorType::Sub => sub_derivative_rule(child, to)?,
-                    OperatorType::Mul => mul_derivative_rule(child, to)?,
-                    OperatorType::Div => div_derivative_rule(child, to)?,
-                    OperatorType::Sin => sin_derivative_rule(child, to)?,
-                    OperatorType::Cos => cos_derivative_rule(child, to)?,
-                    OperatorType::Exp => exp_derivative_rule(child, to)?,
-                    OperatorType::Ln => ln_derivative_rule(child, to)?,
-                    OperatorType::Pow => pow_derivative_rule(child, to)?,
-                }
-            }
-        }
-        .num_aggregate();
-        Ok(result)
+        derivative_with(self, to, &mut |expr| expr.derivative(to))
     }
 }
 
@@ -101,10 +140,12 @@ impl<'a> Derivative<'a, Expression> for Expression {
 
 #[cfg(test)]
 mod derivative_tests {
+    use crate::ast::op::operand::Variable;
+    use crate::math_op::unevaluated::{definite_integral, diff, integral};
     use crate::{
         ast::tree::Expression,
         compute::derivative::Derivative,
-        math_op::{cos::cos, exp::exp, ln::ln, sin::sin},
+        math_op::{cos::cos, exp::exp, ln::ln, pow::Pow, sin::sin},
     };
 
     #[test]
@@ -156,4 +197,38 @@ mod derivative_tests {
             assert_eq!(d.to_string(), "1 / x");
         }
     }
+
+    #[test]
+    fn unevaluated_nodes() {
+        let x = Expression::new_variable("x");
+        let t = Expression::new_variable("t");
+        let (vx, vt) = (Variable::new_variable("x"), Variable::new_variable("t"));
+        // d/dx ∫_0^x sin(t) dt = sinx
+        let i = definite_integral(sin(t.clone()), &vt, Expression::zero(), x.clone());
+        assert_eq!(i.derivative(&vx).unwrap().to_string(), "sinx");
+        // d/dx ∫ x * t dt = ∫ t dt
+        let i = integral(x.clone() * t.clone(), &vt);
+        assert_eq!(i.derivative(&vx).unwrap().to_string(), "Integral(t, t)");
+        let i = integral(x.clone() * t.clone(), &vt);
+        assert_eq!(i.derivative(&vt).unwrap().to_string(), "x * t");
+        let d = diff(sin(x.clone()), &vx);
+        assert_eq!(d.derivative(&vx).unwrap().to_string(), "-sinx");
+    }
+
+    #[test]
+    fn constant_powers() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        // no ln(x) in the result, so it is defined at x = 0 too
+        let d = x.clone().pow(3).derivative(&vx).unwrap();
+        assert_eq!(d.to_string(), "3 * x ^ 2");
+        let d = sin(x.clone()).pow(2).derivative(&vx).unwrap();
+        assert_eq!(d.to_string(), "2 * sinx * cosx");
+        // (ln x)^2 stays a power of ln x
+        let d = ln(x.clone()).pow(2).derivative(&vx).unwrap();
+        assert_eq!(d.to_string(), "2 * lnx * 1 / x");
+        // variable exponents still go through exp(r * ln(u))
+        let d = x.clone().pow(x.clone()).derivative(&vx).unwrap();
+        assert!(d.to_string().contains("lnx"));
+    }
 }
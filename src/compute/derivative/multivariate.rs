@@ -0,0 +1,84 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{matrix::Matrix, num_aggregate::NumAggregate},
+};
+
+use super::{cache::DerivativeCache, Derivative, DerivativeError};
+
+/// Column vector of first order partial derivatives.
+pub fn gradient(expr: &Expression, of: &[Variable]) -> Result<Matrix, DerivativeError<Expression>> {
+    let mut column = vec![];
+    for v in of {
+        column.push(expr.clone().derivative(v)?.num_aggregate());
+    }
+    Ok(Matrix::from_column(column))
+}
+
+/// Matrix whose i-th row is the gradient of **exprs[i]**.
+pub fn jacobian(
+    exprs: &[Expression],
+    of: &[Variable],
+) -> Result<Matrix, DerivativeError<Expression>> {
+    let mut m = Matrix::zeros(exprs.len(), of.len());
+    for (i, expr) in exprs.iter().enumerate() {
+        for (j, v) in of.iter().enumerate() {
+            m.set(i, j, expr.clone().derivative(v)?.num_aggregate());
+        }
+    }
+    Ok(m)
+}
+
+/// Matrix of second order partial derivatives. Only the upper triangle
+/// is differentiated, the lower one is filled in by symmetry, and all
+/// entries share one derivative cache.
+pub fn hessian(expr: &Expression, of: &[Variable]) -> Result<Matrix, DerivativeError<Expression>> {
+    let mut cache = DerivativeCache::new();
+    let n = of.len();
+    let mut m = Matrix::zeros(n, n);
+    for i in 0..n {
+        let g = cache.derivative(expr, &of[i])?;
+        for (j, v) in of.iter().enumerate().skip(i) {
+            let d = cache.derivative(&g, v)?.num_aggregate();
+            if i != j {
+                m.set(j, i, d.clone());
+            }
+            m.set(i, j, d);
+        }
+    }
+    Ok(m)
+}
+
+#[cfg(test)]
+mod multivariate_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::sin::sin,
+    };
+
+    use super::{gradient, hessian, jacobian};
+
+    #[test]
+    fn gradient_and_hessian() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let vars = [Variable::new_variable("x"), Variable::new_variable("y")];
+        let f = x.clone() * y.clone() + sin(x.clone());
+        let g = gradient(&f, &vars).unwrap();
+        assert_eq!(g.to_string(), "[[y + cosx], [x]]");
+        let h = hessian(&f, &vars).unwrap();
+        assert_eq!(h.to_string(), "[[-sinx, 1], [1, 0]]");
+    }
+
+    #[test]
+    fn jacobian_of_map() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let vars = [Variable::new_variable("x"), Variable::new_variable("y")];
+        let f = [x.clone() * y.clone(), x.clone() + y.clone(), sin(y.clone())];
+        let j = jacobian(&f, &vars).unwrap();
+        assert_eq!((j.rows(), j.cols()), (3, 2));
+        assert_eq!(j.to_string(), "[[y, x], [1, 1], [0, cosy]]");
+    }
+}
@@ -1,14 +1,13 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-    math_op::sin::sin,
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError, math_op::sin::sin};
 
-pub(crate) fn cos_derivative_rule(
+pub(crate) fn cos_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
     // (cos(u))' = -sin(u)*u'
     let sub = child.pop().unwrap();
-    return Ok(-sin(sub.clone()) * sub.derivative(to)?);
+    return Ok(-sin(sub.clone()) * d(sub)?);
 }
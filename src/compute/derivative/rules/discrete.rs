@@ -0,0 +1,44 @@
+use crate::{
+    ast::tree::Expression,
+    compute::derivative::DerivativeError,
+    math_op::discrete::{factorial, floor},
+};
+
+fn constant_or_error<D>(
+    u: Expression,
+    node: Expression,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    // only integers go in or come out, so there's no derivative unless
+    // the argument doesn't depend on the variable at all
+    if d(u)?.is_zero() {
+        Ok(Expression::zero())
+    } else {
+        Err(DerivativeError { err_src: node })
+    }
+}
+
+pub(crate) fn factorial_derivative_rule<D>(
+    mut child: Vec<Expression>,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let u = child.pop().unwrap();
+    constant_or_error(u.clone(), factorial(u), d)
+}
+
+pub(crate) fn floor_derivative_rule<D>(
+    mut child: Vec<Expression>,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let u = child.pop().unwrap();
+    constant_or_error(u.clone(), floor(u), d)
+}
@@ -1,16 +1,16 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError};
 
-pub(crate) fn div_derivative_rule(
+pub(crate) fn div_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
     // (u/v)' = (u'v - uv')/(v*v)
     let v = child.pop().unwrap();
     let u = child.pop().unwrap();
-    let v_d = v.clone().derivative(to)?;
-    let u_d = u.clone().derivative(to)?;
+    let v_d = d(v.clone())?;
+    let u_d = d(u.clone())?;
     return Ok((u_d * v.clone() - u * v_d) / (v.clone() * v));
 }
@@ -1,14 +1,13 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-    math_op::exp::exp,
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError, math_op::exp::exp};
 
-pub(crate) fn exp_derivative_rule(
+pub(crate) fn exp_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
     // exp(u)' = u'exp(u)
     let u = child.pop().unwrap();
-    return Ok(u.clone().derivative(to)? * exp(u));
+    return Ok(d(u.clone())? * exp(u));
 }
@@ -0,0 +1,29 @@
+use crate::{
+    ast::tree::Expression, compute::derivative::DerivativeError,
+    math_op::function::function_derivative,
+};
+
+pub(crate) fn function_derivative_rule<D>(
+    name: String,
+    wrt: Vec<usize>,
+    child: Vec<Expression>,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    // f(u_1, ..., u_n)' = f_1(u_1, ..., u_n) * u_1' + ... + f_n(u_1, ..., u_n) * u_n'
+    // where f_i is the unevaluated partial derivative in the i-th argument,
+    // f itself may already be one, wrt then lists the arguments it is in.
+    let mut result = Expression::zero();
+    for (idx, u) in child.iter().enumerate() {
+        let u_d = d(u.clone())?;
+        if u_d.is_zero() {
+            continue;
+        }
+        let mut partial = wrt.clone();
+        partial.push(idx);
+        result = result + function_derivative(&name, partial, child.clone()) * u_d;
+    }
+    Ok(result)
+}
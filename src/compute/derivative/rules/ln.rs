@@ -1,13 +1,13 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError};
 
-pub(crate) fn ln_derivative_rule(
+pub(crate) fn ln_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
     // ln(u)' = u'/u
     let u = child.pop().unwrap();
-    return Ok(u.clone().derivative(to)? / u);
+    return Ok(d(u.clone())? / u);
 }
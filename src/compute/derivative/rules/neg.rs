@@ -1,12 +1,12 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError};
 
-pub(crate) fn neg_derivative_rule(
+pub(crate) fn neg_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
-    let sub = child.pop().unwrap().derivative(to)?;
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let sub = d(child.pop().unwrap())?;
     return Ok(-sub);
 }
@@ -1,18 +1,32 @@
 use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
+    ast::tree::Expression,
+    compute::{derivative::DerivativeError, num_aggregate::NumAggregate},
     math_op::{ln::Ln, pow::Pow},
 };
 
-pub(crate) fn pow_derivative_rule(
+pub(crate) fn pow_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
     // (u^r)' = (exp(r*ln(u)))' = (r*ln(u))' * exp(r*ln(u)) = u^r * (r' * ln(u) + r * u' / u)
     let r = child.pop().unwrap();
     let u = child.pop().unwrap();
-    let r_d = r.clone().derivative(to)?;
-    let u_d = u.clone().derivative(to)?;
+    let r_d = d(r.clone())?;
+    let u_d = d(u.clone())?;
+    if r_d.is_zero() {
+        // constant exponent, (u^r)' = r * u^(r - 1) * u' also holds at u = 0
+        let r_1 = (r.clone() - Expression::one()).num_aggregate();
+        return Ok(if r_1.is_zero() {
+            r * u_d
+        } else if r_1.is_one() {
+            r * u * u_d
+        } else {
+            r * u.pow(r_1) * u_d
+        });
+    }
     let ln_u = u.clone().ln();
     let u_pow_r = u.clone().pow(r.clone());
     return Ok(u_pow_r * (r_d * ln_u + r * u_d / u));
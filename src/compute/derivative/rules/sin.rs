@@ -1,14 +1,13 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-    math_op::cos::cos,
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError, math_op::cos::cos};
 
-pub(crate) fn sin_derivative_rule(
+pub(crate) fn sin_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
     // (sin(u))' = cos(u)*u'
     let sub = child.pop().unwrap();
-    return Ok(cos(sub.clone()) * sub.derivative(to)?);
+    return Ok(cos(sub.clone()) * d(sub)?);
 }
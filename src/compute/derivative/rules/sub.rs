@@ -1,13 +1,13 @@
-use crate::{
-    ast::{op::operand::Variable, tree::Expression},
-    compute::derivative::{Derivative, DerivativeError},
-};
+use crate::{ast::tree::Expression, compute::derivative::DerivativeError};
 
-pub(crate) fn sub_derivative_rule(
+pub(crate) fn sub_derivative_rule<D>(
     mut child: Vec<Expression>,
-    to: &Variable,
-) -> Result<Expression, DerivativeError<Expression>> {
-    let r = child.pop().unwrap().derivative(to)?;
-    let l = child.pop().unwrap().derivative(to)?;
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let r = d(child.pop().unwrap())?;
+    let l = d(child.pop().unwrap())?;
     return Ok(l - r);
 }
@@ -0,0 +1,110 @@
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{
+        derivative::{Derivative, DerivativeError},
+        doit::DoIt,
+        substitute::Substitute,
+    },
+    math_op::unevaluated::{definite_integral, integral, limit, sum},
+};
+
+fn bound_of(child: &[Expression]) -> Result<Variable, DerivativeError<Expression>> {
+    Into::<Option<Variable>>::into(child[1].clone()).ok_or(DerivativeError {
+        err_src: child[1].clone(),
+    })
+}
+
+fn at(
+    body: &Expression,
+    of: &Variable,
+    point: &Expression,
+) -> Result<Expression, DerivativeError<Expression>> {
+    body.clone()
+        .substitute(of, point)
+        .map_err(|_| DerivativeError {
+            err_src: body.clone(),
+        })
+}
+
+pub(crate) fn derivative_node_derivative_rule<D>(
+    child: Vec<Expression>,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    // (d/dx f)' = (df/dx)'
+    let of = bound_of(&child)?;
+    let inner = child[0].clone().derivative(&of)?;
+    d(inner)
+}
+
+pub(crate) fn integral_derivative_rule<D>(
+    child: Vec<Expression>,
+    to: &Variable,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let of = bound_of(&child)?;
+    let body = &child[0];
+    if child.len() == 2 {
+        // (∫f dx)' = f when differentiating in x, otherwise ∫f' dx
+        return if of == *to {
+            Ok(body.clone())
+        } else {
+            Ok(integral(d(body.clone())?, &of))
+        };
+    }
+    // Leibniz rule: (∫_a^b f dx)' = f(b) * b' - f(a) * a' + ∫_a^b f' dx
+    let (lower, upper) = (&child[2], &child[3]);
+    let mut result =
+        at(body, &of, upper)? * d(upper.clone())? - at(body, &of, lower)? * d(lower.clone())?;
+    if of != *to {
+        let inner = d(body.clone())?;
+        if !inner.is_zero() {
+            result = result + definite_integral(inner, &of, lower.clone(), upper.clone());
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn limit_node_derivative_rule<D>(
+    child: Vec<Expression>,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    // A limit is only differentiable once it is known.
+    let mut node = limit(child[0].clone(), &bound_of(&child)?, child[2].clone());
+    node.child.extend(child.into_iter().skip(3));
+    let value = node.clone().doit();
+    if value == node {
+        return Err(DerivativeError { err_src: node });
+    }
+    d(value)
+}
+
+pub(crate) fn sum_derivative_rule<D>(
+    child: Vec<Expression>,
+    to: &Variable,
+    d: &mut D,
+) -> Result<Expression, DerivativeError<Expression>>
+where
+    D: FnMut(Expression) -> Result<Expression, DerivativeError<Expression>>,
+{
+    let of = bound_of(&child)?;
+    let (lower, upper) = (&child[2], &child[3]);
+    if lower.contains_variable(to) || upper.contains_variable(to) {
+        // the number of terms depends on the variable
+        return Err(DerivativeError {
+            err_src: sum(child[0].clone(), &of, lower.clone(), upper.clone()),
+        });
+    }
+    if of == *to {
+        return Ok(Expression::zero());
+    }
+    Ok(sum(d(child[0].clone())?, &of, lower.clone(), upper.clone()))
+}
@@ -0,0 +1,170 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::substitute::Substitute,
+    math_op::function::function,
+};
+
+use super::{Derivative, DerivativeError};
+
+/// Declares which variables are functions of other variables, e.g.
+/// y depending on t. Plain **derivative** treats every other variable as
+/// a constant, **total_derivative** consults these declarations.
+#[derive(Debug, Clone, Default)]
+pub struct Dependencies {
+    map: HashMap<String, Vec<Variable>>,
+}
+
+impl Dependencies {
+    pub fn new() -> Dependencies {
+        Dependencies {
+            map: HashMap::new(),
+        }
+    }
+
+    pub fn declare(&mut self, y: &Variable, on: &[Variable]) -> &mut Dependencies {
+        self.map.insert(y.name.clone(), on.to_vec());
+        self
+    }
+
+    pub fn depends_on(&self, y: &Variable) -> Option<&[Variable]> {
+        self.map.get(&y.name).map(|v| v.as_slice())
+    }
+
+    /// The applied function y(t, ...) standing for **name**, arguments
+    /// being dependent themselves are applied in turn, e.g. z(y(t)).
+    fn applied(
+        &self,
+        name: &str,
+        visiting: &mut Vec<String>,
+    ) -> Result<Expression, DerivativeError<Expression>> {
+        let on = match self.map.get(name) {
+            None => return Ok(Expression::new_variable(name)),
+            Some(on) => on,
+        };
+        if visiting.iter().any(|v| v == name) {
+            return Err(DerivativeError {
+                err_src: Expression::new_variable(name),
+            });
+        }
+        visiting.push(name.to_string());
+        let args = on
+            .iter()
+            .map(|v| self.applied(&v.name, visiting))
+            .collect::<Result<Vec<Expression>, _>>()?;
+        visiting.pop();
+        Ok(function(name, args))
+    }
+
+    /// Replace every dependent variable y by the applied function y(t, ...),
+    /// fails on circular declarations.
+    pub fn apply(&self, expr: Expression) -> Result<Expression, DerivativeError<Expression>> {
+        let mut names: Vec<&String> = self.map.keys().collect();
+        names.sort();
+        let mut result = expr;
+        for name in names {
+            let y = Variable::new_variable(name);
+            let applied = self.applied(name, &mut vec![])?;
+            result = result
+                .substitute(&y, &applied)
+                .map_err(|_| DerivativeError {
+                    err_src: Expression::from(y.clone()),
+                })?;
+        }
+        Ok(result)
+    }
+}
+
+/// Derivative of **expr** in **of** where declared dependent variables
+/// follow the chain rule, e.g. d(y^2)/dt = y(t) ^ 2 * (2 * y'(t) / y(t)).
+pub fn total_derivative(
+    expr: &Expression,
+    of: &Variable,
+    dependencies: &Dependencies,
+) -> Result<Expression, DerivativeError<Expression>> {
+    dependencies.apply(expr.clone())?.derivative(of)
+}
+
+#[cfg(test)]
+mod total_derivative_tests {
+    use std::slice;
+
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::derivative::Derivative,
+        math_op::{function::function, sin::sin},
+    };
+
+    use super::{total_derivative, Dependencies};
+
+    #[test]
+    fn chain_rule() {
+        let x = Expression::new_variable("x");
+        let f_g = function("f", vec![function("g", vec![x.clone()])]);
+        let d = f_g.derivative(&Variable::new_variable("x")).unwrap();
+        assert_eq!(d.to_string(), "f'(g(x)) * g'(x)");
+        let h = function("h", vec![x.clone(), sin(x.clone())]);
+        let d = h.derivative(&Variable::new_variable("x")).unwrap();
+        assert_eq!(d.to_string(), "h_1(x, sinx) + h_2(x, sinx) * cosx");
+        let d2 = function("f", vec![x.clone()])
+            .derivative(&Variable::new_variable("x"))
+            .unwrap()
+            .derivative(&Variable::new_variable("x"))
+            .unwrap();
+        assert_eq!(d2.to_string(), "f''(x)");
+        // the derivative is a node of its own, not a function named f'
+        assert_ne!(d2, function("f''", vec![x.clone()]));
+        let y = Expression::new_variable("y");
+        let h = function("h", vec![x.clone(), y.clone()]);
+        let hxy = h
+            .derivative(&Variable::new_variable("y"))
+            .unwrap()
+            .derivative(&Variable::new_variable("x"))
+            .unwrap();
+        let hyx = function("h", vec![x, y])
+            .derivative(&Variable::new_variable("x"))
+            .unwrap()
+            .derivative(&Variable::new_variable("y"))
+            .unwrap();
+        assert_eq!(hxy.to_string(), "h_12(x, y)");
+        assert_eq!(hxy, hyx);
+    }
+
+    #[test]
+    fn dependent_variables() {
+        let t = Variable::new_variable("t");
+        let y = Expression::new_variable("y");
+        let u = Expression::new_variable("u");
+        let expr = sin(y.clone()) * u.clone();
+        // without declarations y is a constant in t
+        assert!(expr.clone().derivative(&t).unwrap().is_zero());
+        let mut dependencies = Dependencies::new();
+        dependencies.declare(&Variable::new_variable("y"), slice::from_ref(&t));
+        let d = total_derivative(&expr, &t, &dependencies).unwrap();
+        assert_eq!(d.to_string(), "cosy(t) * y'(t) * u");
+    }
+
+    #[test]
+    fn chained_dependencies() {
+        let t = Variable::new_variable("t");
+        let y = Variable::new_variable("y");
+        let z = Variable::new_variable("z");
+        let expr = Expression::from(z.clone());
+        // fresh maps, so that every iteration order gets a chance
+        for _ in 0..8 {
+            let mut dependencies = Dependencies::new();
+            dependencies
+                .declare(&z, slice::from_ref(&y))
+                .declare(&y, slice::from_ref(&t));
+            let d = total_derivative(&expr, &t, &dependencies).unwrap();
+            assert_eq!(d.to_string(), "z'(y(t)) * y'(t)");
+        }
+        let mut dependencies = Dependencies::new();
+        dependencies.declare(&z, slice::from_ref(&y));
+        dependencies.declare(&y, &[z]);
+        assert!(total_derivative(&expr, &t, &dependencies).is_err());
+    }
+}
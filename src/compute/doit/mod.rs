@@ -0,0 +1,166 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{
+        op::{operand::Variable, operator::OperatorType},
+        tree::{AstNode, Expression},
+    },
+    smart_num::ToSmartNum,
+};
+
+use super::{
+    derivative::Derivative,
+    limit::{limit_expression::LimitExpression, Direction, LimitTry},
+    num_aggregate::NumAggregate,
+    substitute::Substitute,
+};
+
+/// L'Hôpital rounds tried when evaluating an unevaluated limit.
+const LIMIT_ORDER_TRY: u64 = 4;
+/// Finite sums with more terms than this are left unevaluated.
+const MAX_SUM_TERMS: i64 = 1000;
+
+pub trait DoIt {
+    /// Evaluate unevaluated derivative, limit and sum nodes from the
+    /// innermost outwards. Nodes that no engine can handle, e.g. every
+    /// integral or a sum with symbolic bounds, are left as they are.
+    fn doit(self) -> Self;
+}
+
+impl DoIt for Expression {
+    fn doit(self) -> Self {
+        let descriptor = match &self.root {
+            AstNode::Operand(_) => return self,
+            AstNode::Operator(operator) => operator.descriptor.clone(),
+        };
+        let root = self.root;
+        let child: Vec<Expression> = self.child.into_iter().map(|c| c.doit()).collect();
+        let evaluated = match descriptor {
+            OperatorType::Derivative => derivative_doit(&child),
+            OperatorType::Limit => limit_doit(&child),
+            OperatorType::Sum => sum_doit(&child),
+            _ => None,
+        };
+        match evaluated {
+            Some(expr) => expr,
+            None => Expression { root, child }.num_aggregate(),
+        }
+    }
+}
+
+fn variable_of(expr: &Expression) -> Option<Variable> {
+    expr.clone().into()
+}
+
+fn derivative_doit(child: &[Expression]) -> Option<Expression> {
+    let of = variable_of(&child[1])?;
+    child[0].clone().derivative(&of).ok()
+}
+
+fn limit_doit(child: &[Expression]) -> Option<Expression> {
+    let of = variable_of(&child[1])?;
+    let direction = match child.get(3).and_then(integer_of) {
+        Some(1) => Direction::Right,
+        Some(-1) => Direction::Left,
+        _ => Direction::Both,
+    };
+    match child[0]
+        .clone()
+        .limit(&of, child[2].clone(), direction, LIMIT_ORDER_TRY)
+        .ok()?
+    {
+        LimitExpression::Normal(expr) => Some(expr),
+        l if l.is_zero() => Some(Expression::zero()),
+        _ => None,
+    }
+}
+
+fn sum_doit(child: &[Expression]) -> Option<Expression> {
+    let of = variable_of(&child[1])?;
+    let lower = child[2].clone().num_aggregate();
+    let upper = child[3].clone().num_aggregate();
+    let (lower, upper) = (integer_of(&lower)?, integer_of(&upper)?);
+    if upper.checked_sub(lower)? >= MAX_SUM_TERMS {
+        return None;
+    }
+    let mut result = Expression::zero();
+    for k in lower..=upper {
+        let term = child[0]
+            .clone()
+            .substitute(&of, &Expression::from(k))
+            .ok()?;
+        result = (result + term).num_aggregate();
+    }
+    Some(result)
+}
+
+fn integer_of(expr: &Expression) -> Option<i64> {
+    expr.to_smart_num().ok()?.to_i64()
+}
+
+#[cfg(test)]
+mod doit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::derivative::Derivative,
+        math_op::{
+            cos::cos,
+            pow::Pow,
+            sin::sin,
+            unevaluated::{diff, integral, limit, sum},
+        },
+    };
+
+    use super::DoIt;
+
+    #[test]
+    fn derivative_and_limit() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let d = diff(sin(x.clone()), &vx);
+        assert_eq!(d.clone().doit().to_string(), "cosx");
+        let l = limit(sin(x.clone()) / x.clone(), &vx, Expression::zero());
+        assert_eq!(l.doit().to_string(), "1");
+        // nested: the derivative is evaluated before the limit
+        let l = limit(diff(cos(x.clone()), &vx), &vx, Expression::zero());
+        assert_eq!(l.doit().to_string(), "0");
+        assert_eq!(d.doit(), sin(x.clone()).derivative(&vx).unwrap());
+    }
+
+    #[test]
+    fn finite_sum() {
+        let n = Variable::new_variable("n");
+        let a = Expression::new_variable("a");
+        let s = sum(
+            Expression::from(n.clone()).pow(2),
+            &n,
+            Expression::from(2),
+            Expression::from(4),
+        );
+        assert_eq!(s.doit().to_string(), "29");
+        let s = sum(
+            a.clone() * Expression::from(n.clone()),
+            &n,
+            Expression::one(),
+            a.clone(),
+        );
+        assert_eq!(s.clone().doit(), s);
+        // the number of terms doesn't fit in an i64
+        let s = sum(
+            Expression::from(n.clone()),
+            &n,
+            Expression::from(i64::MIN),
+            Expression::from(i64::MAX),
+        );
+        assert_eq!(s.clone().doit(), s);
+    }
+
+    #[test]
+    fn integral_stays() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let i = integral(diff(x.clone().pow(2), &vx), &vx);
+        let expected = integral(x.clone().pow(2).derivative(&vx).unwrap(), &vx);
+        assert_eq!(i.doit(), expected);
+    }
+}
@@ -0,0 +1,542 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    f64::consts::PI,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    ast::{
+        op::{
+            operand::{AstOperand, Variable},
+            operator::OperatorType,
+        },
+        tree::{AstNode, Expression},
+    },
+    compute::doit::DoIt,
+};
+
+/*
+ * Interval arithmetic with outward rounding: every bound is pushed one
+ * ulp outwards after a rounded operation, and a few more after library
+ * functions that aren't correctly rounded, so that the exact range is
+ * always enclosed. Functions are split into monotonic pieces: sin and cos
+ * reach their extrema at multiples of pi, which are only known up to an
+ * enclosure, and an extremum that might be inside counts as inside.
+ * Arguments outside of the domain of ln, real powers and factorials are
+ * clipped off, the enclosure holds wherever the expression is defined.
+*/
+
+/// Ulps added around the result of exp, ln, sin and cos.
+const LIBM_ULPS: usize = 2;
+
+/// Beyond this size the multiples of pi near an argument aren't told
+/// apart, sin and cos are bounded by [-1, 1].
+const MAX_TRIG_ARGUMENT: f64 = 1e15;
+
+/// Summations with more terms than this are refused.
+const MAX_SUM_TERMS: f64 = 1e6;
+
+/// Pieces a definite integral is split into, each one bounded by its
+/// width times the range of the body over it.
+const INTEGRAL_PIECES: usize = 64;
+
+/// Closed interval [**lo**, **hi**], bounds may be infinite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IntervalError {
+    err_expr: Expression,
+    reason: &'static str,
+}
+
+impl Display for IntervalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot bound {} due to {}", self.err_expr, self.reason)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+fn down(x: f64, ulps: usize) -> f64 {
+    (0..ulps).fold(x, |x, _| x.next_down())
+}
+
+fn up(x: f64, ulps: usize) -> f64 {
+    (0..ulps).fold(x, |x, _| x.next_up())
+}
+
+impl Interval {
+    pub fn new(lo: f64, hi: f64) -> Interval {
+        debug_assert!(lo <= hi, "empty interval [{}, {}]", lo, hi);
+        Interval { lo, hi }
+    }
+
+    pub fn point(x: f64) -> Interval {
+        Interval { lo: x, hi: x }
+    }
+
+    /// The whole real line.
+    pub fn entire() -> Interval {
+        Interval::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// Smallest interval holding **x**, which was rounded to nearest.
+    pub fn around(x: f64) -> Interval {
+        Interval::new(down(x, 1), up(x, 1))
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    pub fn width(&self) -> f64 {
+        up(self.hi - self.lo, 1)
+    }
+
+    /// Largest |x| over the interval.
+    pub fn magnitude(&self) -> f64 {
+        self.lo.abs().max(self.hi.abs())
+    }
+
+    /// Smallest interval holding both.
+    pub fn hull(&self, other: &Interval) -> Interval {
+        Interval::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    /// { |x| : x in self }.
+    pub fn abs(&self) -> Interval {
+        if self.lo >= 0.0 {
+            *self
+        } else if self.hi <= 0.0 {
+            -*self
+        } else {
+            Interval::new(0.0, self.magnitude())
+        }
+    }
+
+    pub fn exp(&self) -> Interval {
+        Interval::new(
+            down(self.lo.exp(), LIBM_ULPS).max(0.0),
+            up(self.hi.exp(), LIBM_ULPS),
+        )
+    }
+
+    /// ln over the positive part, None when there is none.
+    pub fn ln(&self) -> Option<Interval> {
+        if self.hi <= 0.0 {
+            return None;
+        }
+        let lo = if self.lo <= 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            down(self.lo.ln(), LIBM_ULPS)
+        };
+        Some(Interval::new(lo, up(self.hi.ln(), LIBM_ULPS)))
+    }
+
+    /// Enclosure of pi.
+    pub fn pi() -> Interval {
+        Interval::new(PI, up(PI, 1))
+    }
+
+    pub fn cos(&self) -> Interval {
+        // also catches infinite and NaN bounds
+        if !(self.width() < 2.0 * PI && self.magnitude() < MAX_TRIG_ARGUMENT) {
+            return Interval::new(-1.0, 1.0);
+        }
+        let (a, b) = (self.lo.cos(), self.hi.cos());
+        let mut lo = down(a.min(b), LIBM_ULPS).max(-1.0);
+        let mut hi = up(a.max(b), LIBM_ULPS).min(1.0);
+        // cos(k pi) = (-1)^k
+        let (first, last) = ((self.lo / PI).floor() as i64, (self.hi / PI).ceil() as i64);
+        for k in first - 1..=last + 1 {
+            let extremum = Interval::point(k as f64) * Interval::pi();
+            if extremum.hi >= self.lo && extremum.lo <= self.hi {
+                if k % 2 == 0 {
+                    hi = 1.0;
+                } else {
+                    lo = -1.0;
+                }
+            }
+        }
+        Interval::new(lo, hi)
+    }
+
+    /// sin x = cos(x - pi / 2).
+    pub fn sin(&self) -> Interval {
+        (*self - Interval::pi() * Interval::point(0.5)).cos()
+    }
+
+    /// { floor(x) : x in self }.
+    pub fn floor(&self) -> Interval {
+        Interval::new(self.lo.floor(), self.hi.floor())
+    }
+
+    /// n! over the non-negative integers n of self, None when there
+    /// are none.
+    pub fn factorial(&self) -> Option<Interval> {
+        let (lo, hi) = (self.lo.ceil().max(0.0), self.hi.floor());
+        if hi < lo {
+            return None;
+        }
+        Some(Interval::new(factorial(lo).lo, factorial(hi).hi))
+    }
+
+    /// self ^ **n**, even powers of intervals around 0 start at 0.
+    pub fn powi(&self, n: i64) -> Interval {
+        let base = if n % 2 == 0 { self.abs() } else { *self };
+        // x -> x^n is monotone on base, so only the bounds matter
+        let power = |x: f64| {
+            let (mut result, mut square) = (Interval::point(1.0), Interval::point(x));
+            let mut e = n.unsigned_abs();
+            while e > 0 {
+                if e & 1 == 1 {
+                    result = result * square;
+                }
+                square = square * square;
+                e >>= 1;
+            }
+            result
+        };
+        let (lo, hi) = (power(base.lo), power(base.hi));
+        let mut positive = Interval::new(lo.lo.min(hi.lo), lo.hi.max(hi.hi));
+        if n % 2 == 0 {
+            positive.lo = positive.lo.max(0.0);
+        }
+        if n < 0 {
+            Interval::point(1.0) / positive
+        } else {
+            positive
+        }
+    }
+}
+
+/// Enclosure of **n**!, n being a non-negative integer.
+fn factorial(n: f64) -> Interval {
+    if n > 170.0 {
+        return Interval::new(f64::MAX, f64::INFINITY);
+    }
+    (1..=n as u64).fold(Interval::point(1.0), |f, k| f * Interval::point(k as f64))
+}
+
+impl Interval {
+    /// self ^ **y** for real exponents, over the non-negative part of
+    /// self, None when there is none.
+    pub fn pow(&self, y: &Interval) -> Option<Interval> {
+        if y.lo == y.hi && y.lo.fract() == 0.0 && y.lo.abs() < (1_u64 << 53) as f64 {
+            return Some(self.powi(y.lo as i64));
+        }
+        if self.hi < 0.0 {
+            return None;
+        }
+        let base = Interval::new(self.lo.max(0.0), self.hi);
+        if base.hi == 0.0 {
+            // 0^y is 0 for y > 0, 1 for y = 0 and infinite below
+            return Some(if y.lo > 0.0 {
+                Interval::point(0.0)
+            } else {
+                Interval::new(0.0, f64::INFINITY)
+            });
+        }
+        Some((*y * base.ln()?).exp())
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+
+    fn neg(self) -> Self::Output {
+        Interval::new(-self.hi, -self.lo)
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Interval::new(down(self.lo + rhs.lo, 1), up(self.hi + rhs.hi, 1))
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+/// Interval from the candidate bounds **v**, where 0 * infinity counts as 0.
+fn from_candidates(v: [f64; 4]) -> Interval {
+    let v = v.map(|x| if x.is_nan() { 0.0 } else { x });
+    let lo = v.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = v.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    Interval::new(down(lo, 1), up(hi, 1))
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        from_candidates([
+            self.lo * rhs.lo,
+            self.lo * rhs.hi,
+            self.hi * rhs.lo,
+            self.hi * rhs.hi,
+        ])
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+
+    /// The whole real line when **rhs** holds 0.
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.contains(0.0) {
+            return Interval::entire();
+        }
+        from_candidates([
+            self.lo / rhs.lo,
+            self.lo / rhs.hi,
+            self.hi / rhs.lo,
+            self.hi / rhs.hi,
+        ])
+    }
+}
+
+/// Enclosure of the values of **expr** when every variable ranges over
+/// its interval in **bindings**.
+pub fn eval_interval(
+    expr: &Expression,
+    bindings: &HashMap<String, Interval>,
+) -> Result<Interval, IntervalError> {
+    let err = |reason| IntervalError {
+        err_expr: expr.clone(),
+        reason,
+    };
+    let op = match &expr.root {
+        AstNode::Operand(AstOperand::Num(n)) => {
+            let v = n.to_f64();
+            // integers up to 2^53 are exact, anything else may be rounded
+            return Ok(match n.to_i64() {
+                Some(k) if k.unsigned_abs() < 1 << 53 => Interval::point(v),
+                _ => Interval::around(v),
+            });
+        }
+        AstNode::Operand(AstOperand::Variable(v)) => {
+            return bindings
+                .get(&v.name)
+                .copied()
+                .ok_or(err("unbound variable"))
+        }
+        AstNode::Operator(op) => &op.descriptor,
+    };
+    let arg = |idx: usize| eval_interval(&expr.child[idx], bindings);
+    Ok(match op {
+        OperatorType::Neg => -arg(0)?,
+        OperatorType::Add => arg(0)? + arg(1)?,
+        OperatorType::Sub => arg(0)? - arg(1)?,
+        OperatorType::Mul => arg(0)? * arg(1)?,
+        OperatorType::Div => arg(0)? / arg(1)?,
+        OperatorType::Exp => arg(0)?.exp(),
+        OperatorType::Ln => arg(0)?.ln().ok_or(err("ln of non-positive numbers"))?,
+        OperatorType::Sin => arg(0)?.sin(),
+        OperatorType::Cos => arg(0)?.cos(),
+        OperatorType::Pow => arg(0)?
+            .pow(&arg(1)?)
+            .ok_or(err("a real power of negative numbers"))?,
+        OperatorType::Floor => arg(0)?.floor(),
+        OperatorType::Factorial => arg(0)?
+            .factorial()
+            .ok_or(err("a factorial of no non-negative integer"))?,
+        OperatorType::Function(_) | OperatorType::FunctionDerivative(..) => {
+            return Err(err("undefined function"))
+        }
+        OperatorType::Derivative | OperatorType::Limit => {
+            let value = expr.clone().doit();
+            if value == *expr {
+                return Err(err("unevaluated node"));
+            }
+            eval_interval(&value, bindings)?
+        }
+        OperatorType::Sum => interval_sum(expr, bindings)?,
+        OperatorType::Integral => interval_integral(expr, bindings)?,
+    })
+}
+
+fn bound_of(expr: &Expression) -> Result<Variable, IntervalError> {
+    expr.bound_variable().cloned().ok_or(IntervalError {
+        err_expr: expr.clone(),
+        reason: "invalid bound variable",
+    })
+}
+
+/// Sum of the enclosures of every term, the range must be known exactly.
+fn interval_sum(
+    expr: &Expression,
+    bindings: &HashMap<String, Interval>,
+) -> Result<Interval, IntervalError> {
+    let of = bound_of(expr)?;
+    let lower = eval_interval(&expr.child[2], bindings)?;
+    let upper = eval_interval(&expr.child[3], bindings)?;
+    let exact = |b: Interval| b.lo == b.hi && b.lo.fract() == 0.0;
+    if !exact(lower) || !exact(upper) || upper.lo - lower.lo > MAX_SUM_TERMS {
+        return Err(IntervalError {
+            err_expr: expr.clone(),
+            reason: "invalid summation range",
+        });
+    }
+    let mut inner = bindings.clone();
+    let mut result = Interval::point(0.0);
+    let mut k = lower.lo;
+    while k <= upper.lo {
+        inner.insert(of.name.clone(), Interval::point(k));
+        result = result + eval_interval(&expr.child[0], &inner)?;
+        k += 1.0;
+    }
+    Ok(result)
+}
+
+/// Sum over pieces [t_i, t_(i+1)] of (t_(i+1) - t_i) times the range of
+/// the body, or the same over the hull of the bounds when they aren't
+/// known exactly.
+fn interval_integral(
+    expr: &Expression,
+    bindings: &HashMap<String, Interval>,
+) -> Result<Interval, IntervalError> {
+    if expr.child.len() != 4 {
+        return Err(IntervalError {
+            err_expr: expr.clone(),
+            reason: "indefinite integral",
+        });
+    }
+    let of = bound_of(expr)?;
+    let a = eval_interval(&expr.child[2], bindings)?;
+    let b = eval_interval(&expr.child[3], bindings)?;
+    let mut inner = bindings.clone();
+    let mut piece = |from: Interval, to: Interval| {
+        inner.insert(of.name.clone(), from.hull(&to));
+        Ok((to - from) * eval_interval(&expr.child[0], &inner)?)
+    };
+    if a.lo != a.hi || b.lo != b.hi {
+        return piece(a, b);
+    }
+    let node = |i: usize| match i {
+        0 => a,
+        INTEGRAL_PIECES => b,
+        _ => Interval::point(a.lo + (b.lo - a.lo) * i as f64 / INTEGRAL_PIECES as f64),
+    };
+    (0..INTEGRAL_PIECES).try_fold(Interval::point(0.0), |s, i| {
+        Ok(s + piece(node(i), node(i + 1))?)
+    })
+}
+
+#[cfg(test)]
+mod interval_tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{
+            cos::cos,
+            discrete::{factorial, floor},
+            exp::exp,
+            function::function,
+            ln::ln,
+            pow::Pow,
+            sin::sin,
+            unevaluated::{definite_integral, diff, sum},
+        },
+    };
+
+    use super::{eval_interval, Interval};
+
+    fn over(lo: f64, hi: f64) -> HashMap<String, Interval> {
+        HashMap::from([(String::from("x"), Interval::new(lo, hi))])
+    }
+
+    #[test]
+    fn enclosures() {
+        let x = Expression::new_variable("x");
+        let bindings = over(-1.0, 2.0);
+        let r = eval_interval(&x.clone().pow(2), &bindings).unwrap();
+        assert!(r.lo == 0.0 && r.hi >= 4.0 && r.hi < 4.0 + 1e-12);
+        let r = eval_interval(&(x.clone() * x.clone()), &bindings).unwrap();
+        assert!(r.lo <= -2.0 && r.hi >= 4.0);
+        let r = eval_interval(&exp(x.clone()), &bindings).unwrap();
+        assert!(r.contains((-1_f64).exp()) && r.contains(2_f64.exp()));
+        // 1/3 isn't a float, its enclosure has width
+        let third = Expression::one() / Expression::from(3);
+        let r = eval_interval(&third, &HashMap::new()).unwrap();
+        assert!(r.lo < r.hi && r.contains(1.0 / 3.0));
+        let r = eval_interval(&(Expression::one() / x.clone()), &bindings).unwrap();
+        assert_eq!(r, Interval::entire());
+    }
+
+    #[test]
+    fn trigonometric_pieces() {
+        let x = Expression::new_variable("x");
+        // monotonic on [0, 1], the maximum pi / 2 is inside [1, 2]
+        let r = eval_interval(&sin(x.clone()), &over(0.0, 1.0)).unwrap();
+        assert!(r.lo <= 0.0 && r.lo > -1e-15 && r.contains(1_f64.sin()));
+        assert!(r.hi < 1_f64.sin() + 1e-15);
+        let r = eval_interval(&sin(x.clone()), &over(1.0, 2.0)).unwrap();
+        assert!(r.hi == 1.0 && r.lo <= 2_f64.sin() && r.lo > 0.84);
+        let r = eval_interval(&cos(x.clone()), &over(3.0, 3.2)).unwrap();
+        assert!(r.lo == -1.0 && r.hi >= 3_f64.cos() && r.hi < -0.98);
+        let r = eval_interval(&cos(x.clone()), &over(-10.0, 10.0)).unwrap();
+        assert_eq!(r, Interval::new(-1.0, 1.0));
+    }
+
+    #[test]
+    fn domain_clipping() {
+        let x = Expression::new_variable("x");
+        let half = Expression::one() / Expression::from(2);
+        // only x in (0, 4] counts
+        let r = eval_interval(&ln(x.clone()), &over(-1.0, 4.0)).unwrap();
+        assert!(r.lo == f64::NEG_INFINITY && r.contains(4_f64.ln()));
+        assert!(eval_interval(&ln(x.clone()), &over(-2.0, 0.0)).is_err());
+        let r = eval_interval(&x.clone().pow(half.clone()), &over(-1.0, 4.0)).unwrap();
+        assert!(r.lo == 0.0 && r.hi >= 2.0 && r.hi < 2.0 + 1e-12);
+        assert!(eval_interval(&x.clone().pow(half), &over(-2.0, -1.0)).is_err());
+        // integer powers of negative numbers are fine
+        let r = eval_interval(&x.clone().pow(3), &over(-2.0, -1.0)).unwrap();
+        assert!(r.contains(-8.0) && r.contains(-1.0) && r.hi < -0.99);
+        let r = eval_interval(&factorial(x.clone()), &over(-2.5, 4.5)).unwrap();
+        assert!(r.lo == 1.0 && r.contains(24.0) && r.hi < 24.001);
+        let r = eval_interval(&floor(x.clone()), &over(-0.5, 2.5)).unwrap();
+        assert_eq!((r.lo, r.hi), (-1.0, 2.0));
+        let f = function("f", vec![x]);
+        assert!(eval_interval(&f, &over(0.0, 1.0)).is_err());
+    }
+
+    #[test]
+    fn unevaluated_nodes() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let k = Expression::new_variable("k");
+        let vk = Variable::new_variable("k");
+        // sum of k x for k in 1..=4 is 10 x
+        let s = sum(k * x.clone(), &vk, Expression::from(1), Expression::from(4));
+        let r = eval_interval(&s, &over(1.0, 2.0)).unwrap();
+        assert!(r.lo <= 10.0 && r.lo > 9.99 && r.hi >= 20.0 && r.hi < 20.01);
+        // integral of exp over [0, 1] is e - 1
+        let i = definite_integral(exp(x.clone()), &vx, Expression::zero(), Expression::one());
+        let r = eval_interval(&i, &HashMap::new()).unwrap();
+        assert!(r.contains(1_f64.exp() - 1.0) && r.width() < 0.03);
+        let d = diff(sin(x.clone()), &vx);
+        let r = eval_interval(&d, &over(0.0, 0.5)).unwrap();
+        assert!(r.contains(1.0) && r.contains(0.5_f64.cos()) && r.lo > 0.87);
+    }
+}
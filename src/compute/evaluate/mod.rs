@@ -0,0 +1,217 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::ast::{
+    op::{
+        operand::{AstOperand, Variable},
+        operator::OperatorType,
+    },
+    tree::{AstNode, Expression},
+};
+
+use self::unevaluated::{evaluate_integral, evaluate_sum, evaluate_symbolic};
+
+pub mod interval;
+mod unevaluated;
+
+pub trait Evaluate<T> {
+    type Output;
+    /// Numerically evaluate the expression, every variable
+    /// is looked up in **bindings**.
+    fn evaluate(&self, bindings: T) -> Self::Output;
+}
+
+#[derive(Debug, Clone)]
+pub struct EvaluateError {
+    err_expr: Expression,
+    reason: &'static str,
+}
+
+impl Display for EvaluateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cannot evaluate {} due to {}",
+            self.err_expr, self.reason
+        )
+    }
+}
+
+fn check(value: f64, expr: &Expression) -> Result<f64, EvaluateError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(EvaluateError {
+            err_expr: expr.clone(),
+            reason: "value out of domain",
+        })
+    }
+}
+
+/// n! for a non-negative integer n, NaN otherwise.
+fn factorial_value(n: f64) -> f64 {
+    if n < 0.0 || n.fract() != 0.0 {
+        return f64::NAN;
+    }
+    if n > 170.0 {
+        return f64::INFINITY;
+    }
+    (1..=n as u64).map(|k| k as f64).product()
+}
+
+impl Evaluate<&HashMap<String, f64>> for Expression {
+    type Output = Result<f64, EvaluateError>;
+
+    fn evaluate(&self, bindings: &HashMap<String, f64>) -> Self::Output {
+        match &self.root {
+            AstNode::Operand(operand) => match operand {
+                AstOperand::Num(num) => Ok(num.to_f64()),
+                AstOperand::Variable(variable) => {
+                    bindings.get(&variable.name).copied().ok_or(EvaluateError {
+                        err_expr: self.clone(),
+                        reason: "unbound variable",
+                    })
+                }
+            },
+            AstNode::Operator(operator) => {
+                match operator.descriptor {
+                    OperatorType::Function(_) | OperatorType::FunctionDerivative(..) => {
+                        return Err(EvaluateError {
+                            err_expr: self.clone(),
+                            reason: "undefined function",
+                        })
+                    }
+                    OperatorType::Derivative | OperatorType::Limit => {
+                        return evaluate_symbolic(self, bindings)
+                    }
+                    OperatorType::Sum => return evaluate_sum(self, bindings),
+                    OperatorType::Integral => return evaluate_integral(self, bindings),
+                    _ => {}
+                }
+                let mut args = vec![];
+                for c in &self.child {
+                    args.push(c.evaluate(bindings)?);
+                }
+                let value = match operator.descriptor {
+                    OperatorType::Neg => -args[0],
+                    OperatorType::Add => args[0] + args[1],
+                    OperatorType::Sub => args[0] - args[1],
+                    OperatorType::Mul => args[0] * args[1],
+                    OperatorType::Div => args[0] / args[1],
+                    OperatorType::Sin => args[0].sin(),
+                    OperatorType::Cos => args[0].cos(),
+                    OperatorType::Exp => args[0].exp(),
+                    OperatorType::Ln => args[0].ln(),
+                    OperatorType::Pow => args[0].powf(args[1]),
+                    OperatorType::Factorial => factorial_value(args[0]),
+                    OperatorType::Floor => args[0].floor(),
+                    OperatorType::Function(_)
+                    | OperatorType::FunctionDerivative(..)
+                    | OperatorType::Derivative
+                    | OperatorType::Integral
+                    | OperatorType::Limit
+                    | OperatorType::Sum => unreachable!(),
+                };
+                check(value, self)
+            }
+        }
+    }
+}
+
+impl Evaluate<(&Variable, f64)> for Expression {
+    type Output = Result<f64, EvaluateError>;
+
+    fn evaluate(&self, bindings: (&Variable, f64)) -> Self::Output {
+        let mut map = HashMap::new();
+        map.insert(bindings.0.name.clone(), bindings.1);
+        self.evaluate(&map)
+    }
+}
+
+/// Decide whether **expr** is identically zero. Expressions that do not
+/// aggregate to zero are probed numerically at a few points, so this
+/// is a heuristic test for symbolic input.
+pub(crate) fn is_zero_expr(expr: &Expression) -> bool {
+    if expr.is_zero() {
+        return true;
+    }
+    if expr.is_num() {
+        return false;
+    }
+    let variables = expr.free_variables();
+    for probe in [0.613_f64, 1.377, 2.291] {
+        let mut bindings = HashMap::new();
+        for (idx, v) in variables.iter().enumerate() {
+            bindings.insert(v.name.clone(), probe + 0.173 * idx as f64);
+        }
+        match expr.evaluate(&bindings) {
+            Ok(value) if value.abs() < 1e-9 => continue,
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod evaluate_tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{
+            ln::ln,
+            pow::Pow,
+            sin::sin,
+            unevaluated::{definite_integral, diff, integral, sum},
+        },
+    };
+
+    use super::{is_zero_expr, Evaluate};
+
+    #[test]
+    fn evaluate() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let z = sin(x.clone()) * y.clone() + x.clone().pow(2);
+        let mut bindings = HashMap::new();
+        bindings.insert("x".to_string(), 0.5_f64);
+        bindings.insert("y".to_string(), 2.0_f64);
+        let v = z.evaluate(&bindings).unwrap();
+        assert!((v - (0.5_f64.sin() * 2.0 + 0.25)).abs() < 1e-12);
+        assert!(z.evaluate((&Variable::new_variable("x"), 1.0)).is_err());
+        assert!(ln(x)
+            .evaluate((&Variable::new_variable("x"), -1.0))
+            .is_err());
+    }
+
+    #[test]
+    fn zero_test() {
+        let x = Expression::new_variable("x");
+        assert!(is_zero_expr(&(x.clone() * x.clone() - x.clone().pow(2))));
+        assert!(!is_zero_expr(&(x.clone() - Expression::one())));
+        assert!(!is_zero_expr(&Expression::one()));
+    }
+
+    #[test]
+    fn unevaluated_nodes() {
+        let x = Expression::new_variable("x");
+        let t = Expression::new_variable("t");
+        let (vx, vt) = (Variable::new_variable("x"), Variable::new_variable("t"));
+        let n = Variable::new_variable("n");
+        let s = sum(
+            x.clone() / Expression::from(n.clone()),
+            &n,
+            Expression::one(),
+            Expression::from(4),
+        );
+        let v = s.evaluate((&vx, 12.0)).unwrap();
+        assert!((v - 25.0).abs() < 1e-12);
+        let i = definite_integral(sin(t.clone()), &vt, Expression::zero(), x.clone());
+        let v = i.evaluate((&vx, std::f64::consts::PI)).unwrap();
+        assert!((v - 2.0).abs() < 1e-8);
+        assert!(integral(sin(t), &vt).evaluate((&vx, 1.0)).is_err());
+        let d = diff(x.clone().pow(3), &vx);
+        assert!((d.evaluate((&vx, 2.0)).unwrap() - 12.0).abs() < 1e-12);
+    }
+}
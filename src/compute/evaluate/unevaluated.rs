@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::doit::DoIt,
+};
+
+use super::{Evaluate, EvaluateError};
+
+/// Summations with more terms than this are refused.
+const MAX_SUM_TERMS: f64 = 1e6;
+/// Depth of the adaptive Simpson recursion.
+const MAX_SIMPSON_DEPTH: u32 = 40;
+const SIMPSON_EPS: f64 = 1e-10;
+
+fn bound_of(expr: &Expression) -> Result<Variable, EvaluateError> {
+    expr.bound_variable().cloned().ok_or(EvaluateError {
+        err_expr: expr.clone(),
+        reason: "invalid bound variable",
+    })
+}
+
+/// Derivatives and limits are evaluated symbolically first.
+pub(super) fn evaluate_symbolic(
+    expr: &Expression,
+    bindings: &HashMap<String, f64>,
+) -> Result<f64, EvaluateError> {
+    let value = expr.clone().doit();
+    if value == *expr {
+        return Err(EvaluateError {
+            err_expr: expr.clone(),
+            reason: "unevaluated node",
+        });
+    }
+    value.evaluate(bindings)
+}
+
+pub(super) fn evaluate_sum(
+    expr: &Expression,
+    bindings: &HashMap<String, f64>,
+) -> Result<f64, EvaluateError> {
+    let of = bound_of(expr)?;
+    let lower = expr.child[2].evaluate(bindings)?;
+    let upper = expr.child[3].evaluate(bindings)?;
+    if lower.fract() != 0.0 || upper.fract() != 0.0 || upper - lower > MAX_SUM_TERMS {
+        return Err(EvaluateError {
+            err_expr: expr.clone(),
+            reason: "invalid summation range",
+        });
+    }
+    let mut inner = bindings.clone();
+    let mut result = 0.0;
+    let mut k = lower;
+    while k <= upper {
+        inner.insert(of.name.clone(), k);
+        result += expr.child[0].evaluate(&inner)?;
+        k += 1.0;
+    }
+    Ok(result)
+}
+
+pub(super) fn evaluate_integral(
+    expr: &Expression,
+    bindings: &HashMap<String, f64>,
+) -> Result<f64, EvaluateError> {
+    if expr.child.len() != 4 {
+        return Err(EvaluateError {
+            err_expr: expr.clone(),
+            reason: "indefinite integral",
+        });
+    }
+    let of = bound_of(expr)?;
+    let a = expr.child[2].evaluate(bindings)?;
+    let b = expr.child[3].evaluate(bindings)?;
+    let mut inner = bindings.clone();
+    let mut f = |t: f64| {
+        inner.insert(of.name.clone(), t);
+        expr.child[0].evaluate(&inner)
+    };
+    let (fa, fm, fb) = (f(a)?, f((a + b) / 2.0)?, f(b)?);
+    let whole = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+    simpson(
+        &mut f,
+        (a, fa),
+        (b, fb),
+        fm,
+        whole,
+        SIMPSON_EPS,
+        MAX_SIMPSON_DEPTH,
+    )
+}
+
+/// Adaptive Simpson quadrature on [a, b], **whole** is the Simpson
+/// estimate of the full interval and **fm** the value at its midpoint.
+fn simpson<F>(
+    f: &mut F,
+    (a, fa): (f64, f64),
+    (b, fb): (f64, f64),
+    fm: f64,
+    whole: f64,
+    eps: f64,
+    depth: u32,
+) -> Result<f64, EvaluateError>
+where
+    F: FnMut(f64) -> Result<f64, EvaluateError>,
+{
+    let m = (a + b) / 2.0;
+    let (lm, rm) = ((a + m) / 2.0, (m + b) / 2.0);
+    let (flm, frm) = (f(lm)?, f(rm)?);
+    let left = (m - a) / 6.0 * (fa + 4.0 * flm + fm);
+    let right = (b - m) / 6.0 * (fm + 4.0 * frm + fb);
+    let delta = left + right - whole;
+    if depth == 0 || delta.abs() <= 15.0 * eps {
+        return Ok(left + right + delta / 15.0);
+    }
+    Ok(
+        simpson(f, (a, fa), (m, fm), flm, left, eps / 2.0, depth - 1)?
+            + simpson(f, (m, fm), (b, fb), frm, right, eps / 2.0, depth - 1)?,
+    )
+}
@@ -0,0 +1,267 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{
+        op::{operand::Variable, operator::OperatorType},
+        tree::{AstNode, Expression},
+    },
+    compute::{evaluate::is_zero_expr, num_aggregate::NumAggregate, substitute::Substitute},
+    math_op::{exp::exp, ln::ln},
+};
+
+use super::{
+    limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+    Direction, LimitTarget, LimitTry,
+};
+
+/// How f grows against g as x -> +infinity, read off the limit of f / g.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsymptoticRelation {
+    /// f << g, f / g -> 0.
+    Smaller,
+    /// f >> g, f / g -> infinity.
+    Larger,
+    /// f ~ g, f / g -> 1.
+    Equivalent,
+    /// f ~ c * g for a constant c other than 0 and 1.
+    Proportional(Expression),
+}
+
+impl AsymptoticRelation {
+    /// f = Θ(g), f / g tends to a non-zero constant.
+    pub fn is_theta(&self) -> bool {
+        matches!(
+            self,
+            AsymptoticRelation::Equivalent | AsymptoticRelation::Proportional(_)
+        )
+    }
+
+    /// The constant c in f ~ c * g, when there is one.
+    pub fn constant(&self) -> Option<Expression> {
+        match self {
+            AsymptoticRelation::Equivalent => Some(Expression::one()),
+            AsymptoticRelation::Proportional(c) => Some(c.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AsymptoticRelation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AsymptoticRelation::Smaller => write!(f, "<<"),
+            AsymptoticRelation::Larger => write!(f, ">>"),
+            AsymptoticRelation::Equivalent => write!(f, "~"),
+            AsymptoticRelation::Proportional(c) => write!(f, "~ {} *", c),
+        }
+    }
+}
+
+/// Compare the growth of **f** and **g** as **x** -> +infinity.
+///
+/// L'Hopital's rule never gets rid of an exp, so when f / g has no limit
+/// and both are unbounded they are compared on a log scale instead. Nor
+/// does it get rid of a power of ln x, which x := exp(x) turns into a
+/// power of x.
+pub fn compare_growth(
+    f: &Expression,
+    g: &Expression,
+    x: &Variable,
+    order_try: u64,
+) -> Result<AsymptoticRelation, NoValidLimitFound> {
+    compare_by_limit(f, g, x, order_try).or_else(|e| {
+        compare_log_scale(f, g, x, order_try)
+            .or_else(|| compare_exp_scale(f, g, x, order_try))
+            .ok_or(e)
+    })
+}
+
+fn compare_by_limit(
+    f: &Expression,
+    g: &Expression,
+    x: &Variable,
+    order_try: u64,
+) -> Result<AsymptoticRelation, NoValidLimitFound> {
+    let l = (f.clone() / g.clone())
+        .limit(x, LimitTarget::PlusInfinity, Direction::Both, order_try)?
+        .without_order();
+    match l {
+        l if l.is_zero() => Ok(AsymptoticRelation::Smaller),
+        l if l.is_infinite() => Ok(AsymptoticRelation::Larger),
+        LimitExpression::Normal(c) if is_zero_expr(&(c.clone() - Expression::one())) => {
+            Ok(AsymptoticRelation::Equivalent)
+        }
+        LimitExpression::Normal(c) => Ok(AsymptoticRelation::Proportional(c)),
+        _ => Err(NoValidLimitFound {}),
+    }
+}
+
+/// ln |f| / ln |g| -> L < 1 means f << g and L > 1 means f >> g, as long
+/// as both f and g are unbounded.
+fn compare_log_scale(
+    f: &Expression,
+    g: &Expression,
+    x: &Variable,
+    order_try: u64,
+) -> Option<AsymptoticRelation> {
+    let at_infinity = |e: Expression| {
+        e.limit(x, LimitTarget::PlusInfinity, Direction::Both, order_try)
+            .map(|l| l.without_order())
+    };
+    // only the size matters, a side tending to -infinity is flipped
+    let size = |e: &Expression| match at_infinity(e.clone()) {
+        Ok(LimitExpression::PlusInfinity) => Some(e.clone()),
+        Ok(LimitExpression::MinusInfinity) => Some(-e.clone()),
+        _ => None,
+    };
+    let (f, g) = (size(f)?, size(g)?);
+    let log_ratio = (ln(f) / ln(g)).num_aggregate();
+    let sign = match at_infinity(log_ratio).ok()? {
+        l if l.is_zero() => Sign::Negative,
+        LimitExpression::PlusInfinity => Sign::Positive,
+        LimitExpression::Normal(c) => Sign::of(&(c - Expression::one())),
+        _ => Sign::Unknown,
+    };
+    match sign {
+        Sign::Negative => Some(AsymptoticRelation::Smaller),
+        Sign::Positive => Some(AsymptoticRelation::Larger),
+        Sign::Unknown => None,
+    }
+}
+
+/// f(exp(x)) against g(exp(x)), which grow alike as f against g since
+/// exp(x) -> +infinity monotonically.
+fn compare_exp_scale(
+    f: &Expression,
+    g: &Expression,
+    x: &Variable,
+    order_try: u64,
+) -> Option<AsymptoticRelation> {
+    let at_exp = |e: &Expression| {
+        e.clone()
+            .substitute(x, &exp(Expression::from(x.clone())))
+            .ok()
+            .map(|e| e.num_aggregate())
+    };
+    let (f, g) = (at_exp(f)?, at_exp(g)?);
+    compare_by_limit(&f, &g, x, order_try)
+        .ok()
+        .or_else(|| compare_log_scale(&f, &g, x, order_try))
+}
+
+/// Summands of **expr**, with the sign of subtracted ones folded in.
+fn summands(expr: &Expression, terms: &mut Vec<Expression>) {
+    match &expr.root {
+        AstNode::Operator(op) if op.descriptor == OperatorType::Add => {
+            summands(&expr.child[0], terms);
+            summands(&expr.child[1], terms);
+        }
+        AstNode::Operator(op) if op.descriptor == OperatorType::Sub => {
+            summands(&expr.child[0], terms);
+            let mut rest = vec![];
+            summands(&expr.child[1], &mut rest);
+            terms.extend(rest.into_iter().map(|t| -t));
+        }
+        _ => terms.push(expr.clone()),
+    }
+}
+
+/// The part of **expr** that dominates as **x** -> +infinity. Of a sum
+/// it is the summands no other one outgrows, added up when several are
+/// proportional, and products and quotients are taken factor by factor.
+/// Fails when two summands can't be compared.
+pub fn dominant_term(
+    expr: &Expression,
+    x: &Variable,
+    order_try: u64,
+) -> Result<Expression, NoValidLimitFound> {
+    if !expr.contains_variable(x) {
+        return Ok(expr.clone());
+    }
+    let descriptor = match &expr.root {
+        AstNode::Operator(op) => &op.descriptor,
+        AstNode::Operand(_) => return Ok(expr.clone()),
+    };
+    let child = &expr.child;
+    let term = match descriptor {
+        OperatorType::Neg => -dominant_term(&child[0], x, order_try)?,
+        OperatorType::Mul => {
+            dominant_term(&child[0], x, order_try)? * dominant_term(&child[1], x, order_try)?
+        }
+        OperatorType::Div => {
+            dominant_term(&child[0], x, order_try)? / dominant_term(&child[1], x, order_try)?
+        }
+        OperatorType::Add | OperatorType::Sub => {
+            let mut terms = vec![];
+            summands(expr, &mut terms);
+            let mut leading: Vec<Expression> = vec![];
+            for t in terms {
+                let t = dominant_term(&t, x, order_try)?;
+                match leading.first() {
+                    None => leading.push(t),
+                    Some(l) => match compare_growth(&t, l, x, order_try)? {
+                        AsymptoticRelation::Smaller => {}
+                        AsymptoticRelation::Larger => leading = vec![t],
+                        _ => leading.push(t),
+                    },
+                }
+            }
+            let sum = leading
+                .into_iter()
+                .reduce(|a, b| a + b)
+                .ok_or(NoValidLimitFound {})?;
+            if is_zero_expr(&sum) {
+                // the leading summands cancel, what is left can't be told
+                return Err(NoValidLimitFound {});
+            }
+            sum
+        }
+        _ => expr.clone(),
+    };
+    Ok(term.num_aggregate())
+}
+
+#[cfg(test)]
+mod asymptotic_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{exp::exp, ln::ln, pow::Pow},
+    };
+
+    use super::{compare_growth, dominant_term, AsymptoticRelation};
+
+    #[test]
+    fn growth_classes() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let r = compare_growth(&x.clone().pow(3), &exp(x.clone()), &vx, 2).unwrap();
+        assert_eq!(r, AsymptoticRelation::Smaller);
+        let r = compare_growth(&x, &ln(x.clone()), &vx, 2).unwrap();
+        assert_eq!(r, AsymptoticRelation::Larger);
+        let r = compare_growth(&(ln(x.clone()) * ln(x.clone())), &x, &vx, 2).unwrap();
+        assert_eq!(r, AsymptoticRelation::Smaller);
+        let r = compare_growth(&ln(x.clone()).pow(2), &x, &vx, 2).unwrap();
+        assert_eq!(r, AsymptoticRelation::Smaller);
+        let r = compare_growth(&(x.clone() + ln(x.clone())), &x, &vx, 2).unwrap();
+        assert_eq!(r, AsymptoticRelation::Equivalent);
+        let f = Expression::from(3) * x.clone().pow(2) + x.clone();
+        let r = compare_growth(&f, &x.clone().pow(2), &vx, 2).unwrap();
+        assert!(r.is_theta());
+        assert_eq!(r.constant().unwrap().to_string(), "3");
+    }
+
+    #[test]
+    fn dominant_terms() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let f = x.clone().pow(3) + exp(x.clone()) - ln(x.clone());
+        assert_eq!(dominant_term(&f, &vx, 2).unwrap(), exp(x.clone()));
+        let f = (x.clone().pow(2) + x.clone()) / (ln(x.clone()) + Expression::one());
+        assert_eq!(
+            dominant_term(&f, &vx, 2).unwrap().to_string(),
+            "x ^ 2 / lnx"
+        );
+    }
+}
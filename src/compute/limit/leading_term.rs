@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{
+        op::{
+            operand::{AstOperand, Variable},
+            operator::OperatorType,
+        },
+        tree::{AstNode, Expression},
+    },
+    compute::{
+        evaluate::{is_zero_expr, Evaluate},
+        num_aggregate::NumAggregate,
+    },
+    math_op::{cos::cos, exp::exp, ln::ln, pow::Pow, sin::sin},
+    smart_num::{SmartNum, ToSmartNum},
+};
+
+use super::limit_expression::{LimitExpression, Sign};
+
+/// f ~ **coefficient** * x ^ **exponent** for x -> 0+.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeadingTerm {
+    pub coefficient: Expression,
+    pub exponent: SmartNum,
+}
+
+impl LeadingTerm {
+    fn constant(coefficient: Expression) -> Option<LeadingTerm> {
+        if is_zero_expr(&coefficient) {
+            None
+        } else {
+            Some(LeadingTerm {
+                coefficient,
+                exponent: SmartNum::zero(),
+            })
+        }
+    }
+
+    pub fn limit(&self) -> LimitExpression {
+        LimitExpression::term(self.coefficient.clone(), self.exponent)
+    }
+}
+
+/// Leading term of **expr** as **of** -> 0+, for expressions built from
+/// arithmetic, constant powers and sin, cos, exp, ln of arguments that
+/// stay finite. None when it can't be told, e.g. when leading
+/// coefficients cancel.
+pub fn leading_term(expr: &Expression, of: &Variable) -> Option<LeadingTerm> {
+    if !expr.contains_variable(of) {
+        return LeadingTerm::constant(expr.clone());
+    }
+    let operator = match &expr.root {
+        AstNode::Operand(AstOperand::Variable(_)) => {
+            return Some(LeadingTerm {
+                coefficient: Expression::one(),
+                exponent: SmartNum::one(),
+            })
+        }
+        AstNode::Operand(AstOperand::Num(_)) => return LeadingTerm::constant(expr.clone()),
+        AstNode::Operator(operator) => operator,
+    };
+    let child = &expr.child;
+    let term = match operator.descriptor {
+        OperatorType::Neg => {
+            let u = leading_term(&child[0], of)?;
+            LeadingTerm {
+                coefficient: -u.coefficient,
+                exponent: u.exponent,
+            }
+        }
+        OperatorType::Add => add(&child[0], &child[1], of)?,
+        OperatorType::Sub => add(&child[0], &-child[1].clone(), of)?,
+        OperatorType::Mul => {
+            let (l, r) = (leading_term(&child[0], of)?, leading_term(&child[1], of)?);
+            LeadingTerm {
+                coefficient: l.coefficient * r.coefficient,
+                exponent: l.exponent + r.exponent,
+            }
+        }
+        OperatorType::Div => {
+            let (l, r) = (leading_term(&child[0], of)?, leading_term(&child[1], of)?);
+            LeadingTerm {
+                coefficient: l.coefficient / r.coefficient,
+                exponent: l.exponent - r.exponent,
+            }
+        }
+        OperatorType::Pow => {
+            let k = (&child[1]).to_smart_num().ok()?;
+            let u = leading_term(&child[0], of)?;
+            if k.to_i64().is_none() && Sign::of(&u.coefficient) != Sign::Positive {
+                return None;
+            }
+            LeadingTerm {
+                coefficient: u.coefficient.pow(Expression::from(*k)),
+                exponent: u.exponent * *k,
+            }
+        }
+        OperatorType::Sin | OperatorType::Cos | OperatorType::Exp | OperatorType::Ln => {
+            let u = leading_term(&child[0], of)?;
+            let e = u.exponent.to_f64();
+            if e < 0.0 {
+                return None;
+            }
+            let at = if e > 0.0 {
+                Expression::zero()
+            } else {
+                u.coefficient.clone()
+            };
+            match operator.descriptor {
+                // sin(u) ~ u for u -> 0
+                OperatorType::Sin if e > 0.0 => u,
+                OperatorType::Sin => LeadingTerm::constant(sin(at).num_aggregate())?,
+                OperatorType::Cos => LeadingTerm::constant(cos(at).num_aggregate())?,
+                OperatorType::Exp => LeadingTerm::constant(exp(at).num_aggregate())?,
+                // ln(u) ~ u - 1 for u -> 1
+                OperatorType::Ln if e == 0.0 && u.coefficient.is_one() => {
+                    leading_term(&(child[0].clone() - Expression::one()), of)?
+                }
+                OperatorType::Ln if e == 0.0 => LeadingTerm::constant(ln(at).num_aggregate())?,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let coefficient = term.coefficient.num_aggregate();
+    // a coefficient that cancelled to 0, or came from dividing by one,
+    // says nothing about the leading term
+    if coefficient.free_variables().is_empty() {
+        match coefficient.evaluate(&HashMap::new()) {
+            Ok(c) if c.is_finite() && c != 0.0 => {}
+            _ => return None,
+        }
+    }
+    Some(LeadingTerm {
+        coefficient,
+        exponent: term.exponent,
+    })
+}
+
+fn add(l: &Expression, r: &Expression, of: &Variable) -> Option<LeadingTerm> {
+    if l.is_zero() {
+        return leading_term(r, of);
+    }
+    if r.is_zero() {
+        return leading_term(l, of);
+    }
+    let (l, r) = (leading_term(l, of)?, leading_term(r, of)?);
+    if l.exponent == r.exponent {
+        let coefficient = (l.coefficient + r.coefficient).num_aggregate();
+        if is_zero_expr(&coefficient) {
+            return None;
+        }
+        Some(LeadingTerm {
+            coefficient,
+            exponent: l.exponent,
+        })
+    } else if l.exponent.to_f64() < r.exponent.to_f64() {
+        Some(l)
+    } else {
+        Some(r)
+    }
+}
+
+#[cfg(test)]
+mod leading_term_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{pow::Pow, sin::sin},
+        smart_num::SmartNum,
+    };
+
+    use super::leading_term;
+
+    #[test]
+    fn leading_terms() {
+        let x = Expression::new_variable("x");
+        let of = Variable::new_variable("x");
+        let f = Expression::from(3) / x.clone().pow(2) + Expression::one();
+        let t = leading_term(&f, &of).unwrap();
+        assert_eq!(t.coefficient.to_string(), "3");
+        assert_eq!(t.exponent, SmartNum::from(-2));
+        let f = sin(x.clone()) * x.clone() - x.clone().pow(3);
+        let t = leading_term(&f, &of).unwrap();
+        assert_eq!(t.coefficient.to_string(), "1");
+        assert_eq!(t.exponent, SmartNum::from(2));
+        // leading coefficients cancel
+        assert!(leading_term(&(sin(x.clone()) - x), &of).is_none());
+    }
+}
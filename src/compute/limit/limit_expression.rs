@@ -1,36 +1,267 @@
 #![allow(dead_code)]
 
 use std::{
+    collections::HashMap,
     fmt::Display,
     ops::{Add, Div, Mul, Neg, Sub},
 };
 
-use crate::ast::tree::Expression;
+use crate::{
+    ast::tree::Expression,
+    compute::{
+        evaluate::{is_zero_expr, Evaluate},
+        num_aggregate::NumAggregate,
+    },
+    smart_num::SmartNum,
+};
+
+use super::leading_term::LeadingTerm;
 
 /**
- * If we specify sign of infinity(infinitesimal), a few more
- * rules could be used to compute limit expression arithmetics.
- * But that's too difficult. I'll add those stuffs in the future.
+ * Infinities and infinitesimals carry the side they come from whenever
+ * it can be told, e.g. 1/x for x -> 0+ is +infinity while 1/(a * x)
+ * is an unsigned infinity as long as the sign of a is unknown.
+ *
+ * When the rate is known as well they are kept as a **Term**, c * x ^ e
+ * in the shifted variable x -> 0+, so that ratios and cancellations of
+ * two of them can be resolved, e.g. x^2 / x for x -> 0 is 0.
 */
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Positive,
+    Negative,
+    Unknown,
+}
+
 #[derive(Debug, Clone)]
 pub enum LimitExpression {
-    Infinitesimal,
+    /// Infinitesimal of unknown order.
+    Infinitesimal(Sign),
+    /// Infinity of unknown sign.
     Infinity,
+    PlusInfinity,
+    MinusInfinity,
     BoundedFluctuation,
     Normal(Expression), // Non-zero expression
+    /// Both one-sided limits exist but differ, so the limit does not.
+    SidesDiffer(Box<LimitExpression>, Box<LimitExpression>),
+    /// Infinitesimal (exponent > 0) or infinity (exponent < 0) of known order.
+    Term(LeadingTerm),
+}
+
+/// Limits are equal when they describe the same value, their orders
+/// are not compared.
+impl PartialEq for LimitExpression {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.clone().without_order(), other.clone().without_order()) {
+            (LimitExpression::Infinitesimal(a), LimitExpression::Infinitesimal(b)) => a == b,
+            (LimitExpression::Infinity, LimitExpression::Infinity)
+            | (LimitExpression::PlusInfinity, LimitExpression::PlusInfinity)
+            | (LimitExpression::MinusInfinity, LimitExpression::MinusInfinity)
+            | (LimitExpression::BoundedFluctuation, LimitExpression::BoundedFluctuation) => true,
+            (LimitExpression::Normal(a), LimitExpression::Normal(b)) => a == b,
+            (LimitExpression::SidesDiffer(a, b), LimitExpression::SidesDiffer(c, d)) => {
+                a == c && b == d
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct NoValidLimitFound {}
 
+impl Display for NoValidLimitFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No valid limit found")
+    }
+}
+
+impl Neg for Sign {
+    type Output = Sign;
+
+    fn neg(self) -> Self::Output {
+        match self {
+            Sign::Positive => Sign::Negative,
+            Sign::Negative => Sign::Positive,
+            Sign::Unknown => Sign::Unknown,
+        }
+    }
+}
+
+impl Mul for Sign {
+    type Output = Sign;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (Sign::Unknown, _) | (_, Sign::Unknown) => Sign::Unknown,
+            (a, b) if a == b => Sign::Positive,
+            _ => Sign::Negative,
+        }
+    }
+}
+
+impl Sign {
+    /// Sign of a constant expression, unknown if it has free variables.
+    pub fn of(expr: &Expression) -> Sign {
+        match expr.evaluate(&HashMap::new()) {
+            Ok(v) if v > 0.0 => Sign::Positive,
+            Ok(v) if v < 0.0 => Sign::Negative,
+            _ => Sign::Unknown,
+        }
+    }
+}
+
 impl Display for LimitExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            LimitExpression::Infinitesimal => write!(f, "0"),
+            LimitExpression::Infinitesimal(_) => write!(f, "0"),
             LimitExpression::Infinity => write!(f, "infinity"),
+            LimitExpression::PlusInfinity => write!(f, "+infinity"),
+            LimitExpression::MinusInfinity => write!(f, "-infinity"),
             LimitExpression::BoundedFluctuation => write!(f, "~"),
             LimitExpression::Normal(r) => write!(f, "{}", r),
+            LimitExpression::SidesDiffer(l, r) => {
+                write!(f, "does not exist (left {}, right {})", l, r)
+            }
+            LimitExpression::Term(_) => write!(f, "{}", self.clone().without_order()),
+        }
+    }
+}
+
+impl LimitExpression {
+    /// Signed infinity, unsigned when the sign is unknown.
+    pub fn infinity(sign: Sign) -> LimitExpression {
+        match sign {
+            Sign::Positive => LimitExpression::PlusInfinity,
+            Sign::Negative => LimitExpression::MinusInfinity,
+            Sign::Unknown => LimitExpression::Infinity,
+        }
+    }
+
+    /// **coefficient** * x ^ **exponent** for x -> 0+, a plain value
+    /// when the exponent is 0.
+    pub fn term(coefficient: Expression, exponent: SmartNum) -> LimitExpression {
+        let coefficient = coefficient.num_aggregate();
+        if exponent.is_zero() {
+            LimitExpression::Normal(coefficient)
+        } else {
+            LimitExpression::Term(LeadingTerm {
+                coefficient,
+                exponent,
+            })
+        }
+    }
+
+    /// The order of an infinitesimal or infinity, when known.
+    pub fn order(&self) -> Option<&LeadingTerm> {
+        match self {
+            LimitExpression::Term(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// The same limit with its order dropped.
+    pub fn without_order(self) -> LimitExpression {
+        match self {
+            LimitExpression::Term(t) => {
+                let sign = Sign::of(&t.coefficient);
+                if t.exponent.to_f64() > 0.0 {
+                    LimitExpression::Infinitesimal(sign)
+                } else {
+                    LimitExpression::infinity(sign)
+                }
+            }
+            _ => self,
+        }
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        match self {
+            LimitExpression::Infinity
+            | LimitExpression::PlusInfinity
+            | LimitExpression::MinusInfinity => true,
+            LimitExpression::Term(t) => t.exponent.to_f64() < 0.0,
+            _ => false,
+        }
+    }
+
+    /// The value 0, whether reached exactly or approached.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            LimitExpression::Infinitesimal(_) => true,
+            LimitExpression::Normal(r) => r.is_zero(),
+            LimitExpression::Term(t) => t.exponent.to_f64() > 0.0,
+            _ => false,
+        }
+    }
+
+    /// Whether both describe the same value, infinitesimals of either
+    /// sign count as 0 and finite values are compared numerically.
+    pub fn same_value(&self, other: &LimitExpression) -> bool {
+        match (self.clone().without_order(), other.clone().without_order()) {
+            (a, b) if a.is_zero() && b.is_zero() => true,
+            (LimitExpression::Normal(a), LimitExpression::Normal(b)) => is_zero_expr(&(a - b)),
+            (a, b) => a == b,
+        }
+    }
+
+    /// Sign of the values near the limit point.
+    pub fn sign(&self) -> Sign {
+        match self {
+            LimitExpression::Infinitesimal(s) => *s,
+            LimitExpression::Term(t) => Sign::of(&t.coefficient),
+            LimitExpression::PlusInfinity => Sign::Positive,
+            LimitExpression::MinusInfinity => Sign::Negative,
+            LimitExpression::Normal(r) => Sign::of(r),
+            _ => Sign::Unknown,
+        }
+    }
+
+    /// Limit of 1 / f from the limit of f.
+    pub fn recip(self) -> Result<LimitExpression, NoValidLimitFound> {
+        match self {
+            LimitExpression::Infinitesimal(s) => Ok(LimitExpression::infinity(s)),
+            LimitExpression::Infinity => Ok(LimitExpression::Infinitesimal(Sign::Unknown)),
+            LimitExpression::PlusInfinity => Ok(LimitExpression::Infinitesimal(Sign::Positive)),
+            LimitExpression::MinusInfinity => Ok(LimitExpression::Infinitesimal(Sign::Negative)),
+            LimitExpression::Normal(r) if !r.is_zero() => {
+                Ok(LimitExpression::Normal(Expression::one() / r))
+            }
+            LimitExpression::Term(t) => Ok(LimitExpression::term(
+                Expression::one() / t.coefficient,
+                -t.exponent,
+            )),
+            _ => Err(NoValidLimitFound {}),
+        }
+    }
+
+    /// Merge the left and right limits into the two-sided one.
+    pub fn merge_sides(left: LimitExpression, right: LimitExpression) -> LimitExpression {
+        // the orders of the two sides are in different variables
+        let (left, right) = (left.without_order(), right.without_order());
+        match (&left, &right) {
+            (LimitExpression::Infinitesimal(a), LimitExpression::Infinitesimal(b)) => {
+                LimitExpression::Infinitesimal(if a == b { *a } else { Sign::Unknown })
+            }
+            (LimitExpression::Normal(a), LimitExpression::Normal(b))
+                if is_zero_expr(&(a.clone() - b.clone())) =>
+            {
+                left
+            }
+            (LimitExpression::Normal(a), LimitExpression::Infinitesimal(_))
+            | (LimitExpression::Infinitesimal(_), LimitExpression::Normal(a))
+                if a.is_zero() =>
+            {
+                LimitExpression::Infinitesimal(Sign::Unknown)
+            }
+            (LimitExpression::Infinity, b) | (b, LimitExpression::Infinity) if b.is_infinite() => {
+                LimitExpression::Infinity
+            }
+            (a, b) if a.is_infinite() && a == b => left,
+            (LimitExpression::BoundedFluctuation, LimitExpression::BoundedFluctuation) => left,
+            _ => LimitExpression::SidesDiffer(Box::new(left), Box::new(right)),
         }
     }
 }
@@ -41,6 +272,16 @@ impl Neg for LimitExpression {
     fn neg(self) -> Self::Output {
         match self {
             LimitExpression::Normal(r) => LimitExpression::Normal(-r),
+            LimitExpression::Infinitesimal(s) => LimitExpression::Infinitesimal(-s),
+            LimitExpression::Term(t) => LimitExpression::Term(LeadingTerm {
+                coefficient: (-t.coefficient).num_aggregate(),
+                exponent: t.exponent,
+            }),
+            LimitExpression::PlusInfinity => LimitExpression::MinusInfinity,
+            LimitExpression::MinusInfinity => LimitExpression::PlusInfinity,
+            LimitExpression::SidesDiffer(l, r) => {
+                LimitExpression::SidesDiffer(Box::new(-*l), Box::new(-*r))
+            }
             _ => self,
         }
     }
@@ -50,32 +291,93 @@ impl Add for LimitExpression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        match self {
-            LimitExpression::Infinitesimal => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::Infinity => Err(NoValidLimitFound {}),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
-                LimitExpression::Normal(r) => Ok(LimitExpression::Normal(r)),
-            },
-            LimitExpression::Infinity => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Infinity),
-                LimitExpression::Infinity => Err(NoValidLimitFound {}),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::Infinity),
-                LimitExpression::Normal(_) => Ok(LimitExpression::Infinity),
-            },
-            LimitExpression::BoundedFluctuation => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::BoundedFluctuation),
-                LimitExpression::Infinity => Ok(LimitExpression::Infinity),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
-                LimitExpression::Normal(_) => Ok(LimitExpression::BoundedFluctuation),
-            },
-            LimitExpression::Normal(a) => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Normal(a)),
-                LimitExpression::Infinity => Ok(LimitExpression::Infinity),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
-                LimitExpression::Normal(b) => Ok(LimitExpression::Normal(a + b)),
+        match (self, rhs) {
+            (LimitExpression::Term(a), LimitExpression::Term(b)) => add_terms(a, b),
+            (LimitExpression::Term(t), LimitExpression::Normal(r))
+            | (LimitExpression::Normal(r), LimitExpression::Term(t)) => {
+                if r.is_zero() || t.exponent.to_f64() < 0.0 {
+                    Ok(LimitExpression::Term(t))
+                } else {
+                    Ok(LimitExpression::Normal(r))
+                }
+            }
+            (a, b) => add_without_order(a.without_order(), b.without_order()),
+        }
+    }
+}
+
+/// The term with the smaller exponent dominates, equal exponents add up.
+fn add_terms(a: LeadingTerm, b: LeadingTerm) -> Result<LimitExpression, NoValidLimitFound> {
+    if a.exponent != b.exponent {
+        return Ok(LimitExpression::Term(
+            if a.exponent.to_f64() < b.exponent.to_f64() {
+                a
+            } else {
+                b
             },
+        ));
+    }
+    let coefficient = (a.coefficient + b.coefficient).num_aggregate();
+    if !is_zero_expr(&coefficient) {
+        return Ok(LimitExpression::term(coefficient, a.exponent));
+    }
+    if a.exponent.to_f64() > 0.0 {
+        // of higher order, which is not known
+        Ok(LimitExpression::Infinitesimal(Sign::Unknown))
+    } else {
+        Err(NoValidLimitFound {})
+    }
+}
+
+fn add_without_order(
+    a: LimitExpression,
+    b: LimitExpression,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    match (a, b) {
+        (LimitExpression::SidesDiffer(..), _) | (_, LimitExpression::SidesDiffer(..)) => {
+            Err(NoValidLimitFound {})
+        }
+        (LimitExpression::Infinitesimal(a), LimitExpression::Infinitesimal(b)) => {
+            Ok(LimitExpression::Infinitesimal(if a == b {
+                a
+            } else {
+                Sign::Unknown
+            }))
+        }
+        (LimitExpression::Normal(a), LimitExpression::Normal(b)) => {
+            if a.is_zero() {
+                return Ok(LimitExpression::Normal(b));
+            }
+            if b.is_zero() {
+                return Ok(LimitExpression::Normal(a));
+            }
+            let sum = a + b;
+            if is_zero_expr(&sum) {
+                // cancellation, the side it comes from is lost
+                Ok(LimitExpression::Infinitesimal(Sign::Unknown))
+            } else {
+                Ok(LimitExpression::Normal(sum))
+            }
         }
+        (LimitExpression::Normal(r), LimitExpression::Infinitesimal(s))
+        | (LimitExpression::Infinitesimal(s), LimitExpression::Normal(r)) => {
+            if r.is_zero() {
+                Ok(LimitExpression::Infinitesimal(s))
+            } else {
+                Ok(LimitExpression::Normal(r))
+            }
+        }
+        (a, b) if a.is_infinite() && b.is_infinite() => {
+            if a == b && a != LimitExpression::Infinity {
+                Ok(a)
+            } else {
+                Err(NoValidLimitFound {})
+            }
+        }
+        (a, _) if a.is_infinite() => Ok(a),
+        (_, b) if b.is_infinite() => Ok(b),
+        // bounded fluctuation with something finite
+        _ => Ok(LimitExpression::BoundedFluctuation),
     }
 }
 
@@ -91,32 +393,64 @@ impl Mul for LimitExpression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        match self {
-            LimitExpression::Infinitesimal => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::Infinity => Err(NoValidLimitFound {}),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::Normal(_) => Ok(LimitExpression::Infinitesimal),
-            },
-            LimitExpression::Infinity => match rhs {
-                LimitExpression::Infinitesimal => Err(NoValidLimitFound {}),
-                LimitExpression::Infinity => Ok(LimitExpression::Infinity),
-                LimitExpression::BoundedFluctuation => Err(NoValidLimitFound {}), // x * sinx should be infinity of unknown?
-                LimitExpression::Normal(_) => Ok(LimitExpression::Infinity),
-            },
-            LimitExpression::BoundedFluctuation => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::Infinity => Err(NoValidLimitFound {}),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
-                LimitExpression::Normal(_) => Ok(LimitExpression::BoundedFluctuation),
-            },
-            LimitExpression::Normal(a) => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::Infinity => Ok(LimitExpression::Infinity),
-                LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
-                LimitExpression::Normal(b) => Ok(LimitExpression::Normal(a * b)),
-            },
+        match (self, rhs) {
+            (LimitExpression::Term(a), LimitExpression::Term(b)) => Ok(LimitExpression::term(
+                a.coefficient * b.coefficient,
+                a.exponent + b.exponent,
+            )),
+            (LimitExpression::Term(t), LimitExpression::Normal(r))
+            | (LimitExpression::Normal(r), LimitExpression::Term(t))
+                if !r.is_zero() =>
+            {
+                Ok(LimitExpression::term(t.coefficient * r, t.exponent))
+            }
+            (a, b) => mul_without_order(a.without_order(), b.without_order()),
+        }
+    }
+}
+
+fn mul_without_order(
+    a: LimitExpression,
+    b: LimitExpression,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    match (a, b) {
+        (LimitExpression::SidesDiffer(..), _) | (_, LimitExpression::SidesDiffer(..)) => {
+            Err(NoValidLimitFound {})
+        }
+        (LimitExpression::Normal(a), LimitExpression::Normal(b)) => {
+            Ok(LimitExpression::Normal(a * b))
+        }
+        (a, b) if a.is_zero() && b.is_infinite() || a.is_infinite() && b.is_zero() => {
+            Err(NoValidLimitFound {})
+        }
+        (a, b) if a.is_zero() || b.is_zero() => {
+            let exact_zero =
+                |l: &LimitExpression| matches!(l, LimitExpression::Normal(r) if r.is_zero());
+            if exact_zero(&a) || exact_zero(&b) {
+                return Ok(LimitExpression::Normal(Expression::zero()));
+            }
+            // a bounded factor keeps it infinitesimal, but hides the sign
+            let bounded = matches!(a, LimitExpression::BoundedFluctuation)
+                || matches!(b, LimitExpression::BoundedFluctuation);
+            let sign = if bounded {
+                Sign::Unknown
+            } else {
+                a.sign() * b.sign()
+            };
+            Ok(LimitExpression::Infinitesimal(sign))
         }
+        (a, b) if a.is_infinite() || b.is_infinite() => {
+            if matches!(a, LimitExpression::BoundedFluctuation)
+                || matches!(b, LimitExpression::BoundedFluctuation)
+            {
+                // x * sinx has no limit
+                Err(NoValidLimitFound {})
+            } else {
+                Ok(LimitExpression::infinity(a.sign() * b.sign()))
+            }
+        }
+        // bounded fluctuation with something finite and non-zero
+        _ => Ok(LimitExpression::BoundedFluctuation),
     }
 }
 
@@ -126,49 +460,66 @@ impl Div for LimitExpression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
     fn div(self, rhs: Self) -> Self::Output {
-        match self {
-            LimitExpression::Infinitesimal => match rhs {
-                LimitExpression::Infinitesimal => Err(NoValidLimitFound {}),
-                LimitExpression::Infinity => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::BoundedFluctuation => Err(NoValidLimitFound {}), // x/sinx
-                LimitExpression::Normal(r) => {
-                    if r.is_zero() {
-                        Err(NoValidLimitFound {})
-                    } else {
-                        Ok(LimitExpression::Infinitesimal)
-                    }
-                }
-            },
-            LimitExpression::Infinity => match rhs {
-                LimitExpression::Infinitesimal => Ok(LimitExpression::Infinity),
-                LimitExpression::Infinity => Err(NoValidLimitFound {}),
-                LimitExpression::BoundedFluctuation => Err(NoValidLimitFound {}),
-                LimitExpression::Normal(_) => Ok(LimitExpression::Infinity),
-            },
-            LimitExpression::BoundedFluctuation => match rhs {
-                LimitExpression::Infinitesimal => Err(NoValidLimitFound {}),
-                LimitExpression::Infinity => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::BoundedFluctuation => Err(NoValidLimitFound {}),
-                LimitExpression::Normal(r) => {
-                    if r.is_zero() {
-                        Err(NoValidLimitFound {})
-                    } else {
-                        Ok(LimitExpression::BoundedFluctuation)
-                    }
-                }
-            },
-            LimitExpression::Normal(a) => match rhs {
-                LimitExpression::Infinitesimal => {
-                    if !a.is_zero() {
-                        Ok(LimitExpression::Infinity)
-                    } else {
-                        Err(NoValidLimitFound {})
-                    }
-                }
-                LimitExpression::Infinity => Ok(LimitExpression::Infinitesimal),
-                LimitExpression::BoundedFluctuation => Err(NoValidLimitFound {}),
-                LimitExpression::Normal(b) => Ok(LimitExpression::Normal(a / b)),
-            },
-        }
+        Mul::mul(self, rhs.recip()?)
+    }
+}
+
+#[cfg(test)]
+mod limit_expression_tests {
+    use crate::{ast::tree::Expression, smart_num::SmartNum};
+
+    use super::{LimitExpression, Sign};
+
+    #[test]
+    fn signs() {
+        let pos = LimitExpression::Infinitesimal(Sign::Positive);
+        let two = LimitExpression::Normal(Expression::from(2));
+        let minus_two = LimitExpression::Normal(Expression::from(-2));
+        assert_eq!(
+            (two.clone() / pos.clone()).unwrap(),
+            LimitExpression::PlusInfinity
+        );
+        assert_eq!(
+            (minus_two / pos.clone()).unwrap(),
+            LimitExpression::MinusInfinity
+        );
+        let a = LimitExpression::Normal(Expression::new_variable("a"));
+        assert_eq!((a / pos.clone()).unwrap(), LimitExpression::Infinity);
+        assert!((LimitExpression::PlusInfinity + LimitExpression::MinusInfinity).is_err());
+        assert!((pos.clone() / pos).is_err());
+        assert_eq!(
+            (LimitExpression::MinusInfinity * LimitExpression::MinusInfinity).unwrap(),
+            LimitExpression::PlusInfinity
+        );
+        let merged = LimitExpression::merge_sides(
+            LimitExpression::MinusInfinity,
+            LimitExpression::PlusInfinity,
+        );
+        assert_eq!(
+            merged.to_string(),
+            "does not exist (left -infinity, right +infinity)"
+        );
+        assert_eq!(LimitExpression::merge_sides(two.clone(), two.clone()), two);
+    }
+
+    #[test]
+    fn orders() {
+        let x = LimitExpression::term(Expression::one(), SmartNum::one());
+        let x2 = (x.clone() * x.clone()).unwrap();
+        let z = (x2.clone() / x.clone()).unwrap();
+        assert_eq!(z, LimitExpression::Infinitesimal(Sign::Positive));
+        assert_eq!(z.order().unwrap().exponent, SmartNum::one());
+        // 3x^2 / x^2
+        let three = LimitExpression::Normal(Expression::from(3));
+        let z = ((three * x2.clone()).unwrap() / x2.clone()).unwrap();
+        assert_eq!(z.to_string(), "3");
+        // only the leading term is kept, so (x - x^2) - x is of unknown order
+        let z = ((x.clone() - x2.clone()).unwrap() - x.clone()).unwrap();
+        assert!(z.order().is_none());
+        assert!(z.is_zero());
+        // 1/x - 1/x^2 is dominated by -1/x^2
+        let z = (x.clone().recip().unwrap() - x2.recip().unwrap()).unwrap();
+        assert_eq!(z, LimitExpression::MinusInfinity);
+        assert!((x.clone().recip().unwrap() - x.recip().unwrap()).is_err());
     }
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use crate::{
     ast::{
         op::{
@@ -6,55 +8,129 @@ use crate::{
         },
         tree::{AstNode, Expression},
     },
-    compute::num_aggregate::NumAggregate,
+    compute::{doit::DoIt, num_aggregate::NumAggregate, substitute::Substitute},
+    smart_num::SmartNum,
 };
 
 use self::{
     limit_expression::{LimitExpression, NoValidLimitFound},
     rules::{
-        add::AddLimitExprRule, div::DivLimitExprRule, mul::MulLimitExprRule, neg::NegLimitExprRule,
-        sub::SubLimitExprRule, sin::SinLimitExprRule, cos::CosLimitExprRule,
+        add::AddLimitExprRule, cos::CosLimitExprRule, div::DivLimitExprRule, exp::ExpLimitExprRule,
+        ln::LnLimitExprRule, mul::MulLimitExprRule, neg::NegLimitExprRule, pow::PowLimitExprRule,
+        sin::SinLimitExprRule, sub::SubLimitExprRule,
     },
 };
 
+pub mod asymptotic;
+pub mod leading_term;
 pub mod limit_expression;
+pub mod multivariate;
 mod rules;
+pub mod sequence;
+pub mod series;
+
+/// Side from which the limit point is approached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Both,
+}
+
+/// Point a limit is taken at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitTarget {
+    Finite(Expression),
+    PlusInfinity,
+    MinusInfinity,
+}
+
+impl From<Expression> for LimitTarget {
+    fn from(to: Expression) -> Self {
+        LimitTarget::Finite(to)
+    }
+}
 
-trait LimitTry<T, U> {
+pub trait LimitTry<T, U> {
     type Output;
-    fn limit(self, of: T, to: U, order_try: u64) -> Self::Output;
+    /// **order_try** bounds the rounds of L'Hôpital's rule tried on
+    /// indeterminate quotients.
+    fn limit(self, of: T, to: U, direction: Direction, order_try: u64) -> Self::Output;
 }
 
 impl<'a> LimitTry<&'a Variable, Expression> for Expression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
-    fn limit(self, of: &'a Variable, to: Expression, order_try: u64) -> Self::Output {
+    /// Rules only ever see x -> 0+, any other limit is first shifted
+    /// there with x := to + x or x := to - x, and a two-sided limit is
+    /// the merge of both one-sided ones.
+    fn limit(
+        self,
+        of: &'a Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let x = Expression::from(of.clone());
+        let shifted = match direction {
+            Direction::Both => {
+                let left = self
+                    .clone()
+                    .limit(of, to.clone(), Direction::Left, order_try)?;
+                let right = self.limit(of, to, Direction::Right, order_try)?;
+                return Ok(LimitExpression::merge_sides(left, right));
+            }
+            Direction::Right if to.is_zero() => None,
+            Direction::Right => Some(to + x),
+            Direction::Left if to.is_zero() => Some(-x),
+            Direction::Left => Some(to - x),
+        };
+        if let Some(point) = shifted {
+            let z = self
+                .substitute(of, &point)
+                .map_err(|_| NoValidLimitFound {})?;
+            return z.limit(of, Expression::zero(), Direction::Right, order_try);
+        }
+        let to = Expression::zero();
+        let direction = Direction::Right;
         let z = match &self.root {
             AstNode::Operand(operand) => match operand {
                 AstOperand::Num(num) => Ok(LimitExpression::Normal(Expression::from(num.clone()))),
                 AstOperand::Variable(variable) => {
                     if variable.name == of.name {
-                        if to.is_zero() {
-                            Ok(LimitExpression::Infinitesimal)
-                        } else {
-                            Ok(LimitExpression::Normal(to))
-                        }
+                        Ok(LimitExpression::term(Expression::one(), SmartNum::one()))
                     } else {
                         Ok(LimitExpression::Normal(Expression::from(variable.clone())))
                     }
                 }
             },
             AstNode::Operator(operator) => match operator.descriptor {
-                OperatorType::Neg => Ok(self.neg_limit_expr_rule(of, to, order_try)),
-                OperatorType::Add => self.add_limit_expr_rule(of, to, order_try),
-                OperatorType::Sub => self.sub_limit_expr_rule(of, to, order_try),
-                OperatorType::Mul => self.mul_limit_expr_rule(of, to, order_try),
-                OperatorType::Div => self.div_limit_expr_rule(of, to, order_try),
-                OperatorType::Sin => self.sin_limit_expr_rule(of, to, order_try),
-                OperatorType::Cos => self.cos_limit_expr_rule(of, to, order_try),
-                OperatorType::Exp => todo!(),
-                OperatorType::Ln => todo!(),
-                OperatorType::Pow => todo!(),
+                OperatorType::Neg => self.neg_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Add => self.add_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Sub => self.sub_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Mul => self.mul_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Div => self.div_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Sin => self.sin_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Cos => self.cos_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Exp => self.exp_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Ln => self.ln_limit_expr_rule(of, to, direction, order_try),
+                OperatorType::Pow => self.pow_limit_expr_rule(of, to, direction, order_try),
+                // factorial and floor only make sense for sequences
+                OperatorType::Factorial
+                | OperatorType::Floor
+                | OperatorType::Function(_)
+                | OperatorType::FunctionDerivative(..) => Err(NoValidLimitFound {}),
+                OperatorType::Derivative
+                | OperatorType::Integral
+                | OperatorType::Limit
+                | OperatorType::Sum => {
+                    let value = self.clone().doit();
+                    if value == self {
+                        Err(NoValidLimitFound {})
+                    } else {
+                        value.limit(of, to, direction, order_try)
+                    }
+                }
             },
         }?;
         let flag = match &z {
@@ -72,12 +148,177 @@ impl<'a> LimitTry<&'a Variable, Expression> for Expression {
     }
 }
 
+impl<'a> LimitTry<&'a Variable, LimitTarget> for Expression {
+    type Output = Result<LimitExpression, NoValidLimitFound>;
+
+    /// x -> +infinity is x := 1 / x with x -> 0+, and x -> -infinity is
+    /// x := -1 / x. Infinities are only approached from one side, so
+    /// **direction** only matters for finite targets.
+    fn limit(
+        self,
+        of: &'a Variable,
+        to: LimitTarget,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let recip = Expression::one() / Expression::from(of.clone());
+        let point = match to {
+            LimitTarget::Finite(to) => return self.limit(of, to, direction, order_try),
+            LimitTarget::PlusInfinity => recip,
+            LimitTarget::MinusInfinity => -recip,
+        };
+        let z = self
+            .substitute(of, &point)
+            .map_err(|_| NoValidLimitFound {})?;
+        z.limit(of, Expression::zero(), Direction::Right, order_try)
+    }
+}
+
 impl LimitTry<Expression, Expression> for Expression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
-    fn limit(self, of_: Expression, to: Expression, order_try: u64) -> Self::Output {
+    fn limit(
+        self,
+        of_: Expression,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
         let of_raw: Option<Variable> = of_.into();
         let of = of_raw.unwrap();
-        self.limit(&of, to, order_try)
+        self.limit(&of, to, direction, order_try)
+    }
+}
+
+#[cfg(test)]
+mod limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::{
+            limit_expression::{LimitExpression, Sign},
+            Direction, LimitTarget, LimitTry,
+        },
+        math_op::{cos::cos, pow::Pow, sin::sin},
+        smart_num::SmartNum,
+    };
+
+    #[test]
+    fn one_sided() {
+        let x = Expression::new_variable("x");
+        let y = Expression::one() / x.clone();
+        let right = y
+            .clone()
+            .limit(x.clone(), Expression::zero(), Direction::Right, 1);
+        let left = y
+            .clone()
+            .limit(x.clone(), Expression::zero(), Direction::Left, 1);
+        assert_eq!(right.unwrap(), LimitExpression::PlusInfinity);
+        assert_eq!(left.unwrap(), LimitExpression::MinusInfinity);
+        let both = y
+            .limit(x.clone(), Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert!(matches!(both, LimitExpression::SidesDiffer(..)));
+        // -1 / x^2 diverges to -infinity from both sides
+        let y = -(Expression::one() / (x.clone() * x.clone()));
+        let both = y
+            .limit(x.clone(), Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(both, LimitExpression::MinusInfinity);
+        // 1 / (x - 1) at 1 from the left
+        let y = Expression::one() / (x.clone() - Expression::one());
+        let left = y.limit(x, Expression::one(), Direction::Left, 1).unwrap();
+        assert_eq!(left, LimitExpression::MinusInfinity);
+    }
+
+    #[test]
+    fn at_infinity() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let three = Expression::from(3);
+        let x2 = x.clone() * x.clone();
+        let y = (three * x2.clone() + Expression::one()) / (x2.clone() - Expression::from(2));
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "3");
+        let y = x.clone() * sin(Expression::one() / x.clone());
+        let l = y
+            .clone()
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "1");
+        let l = y
+            .limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "1");
+        let y = x.clone() - x2.clone();
+        let l = y
+            .clone()
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l, LimitExpression::MinusInfinity);
+        let l = y
+            .limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l, LimitExpression::MinusInfinity);
+        let y = x2.clone() * x.clone() / (Expression::one() - x2);
+        let l = y
+            .limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l, LimitExpression::PlusInfinity);
+        let y = Expression::one() / x.clone();
+        let l = y
+            .limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l, LimitExpression::Infinitesimal(Sign::Negative));
+    }
+
+    #[test]
+    fn signed_infinitesimals() {
+        let x = Expression::new_variable("x");
+        let y = -Expression::from(x.clone());
+        let right = y
+            .limit(x.clone(), Expression::zero(), Direction::Right, 1)
+            .unwrap();
+        assert_eq!(right, LimitExpression::Infinitesimal(Sign::Negative));
+        // the sign of sin(u) is the one of u for u -> 0
+        let y = -sin(x.clone());
+        let right = y
+            .limit(x.clone(), Expression::zero(), Direction::Right, 1)
+            .unwrap();
+        assert_eq!(right, LimitExpression::Infinitesimal(Sign::Negative));
+        // removable singularity away from 0
+        let y = (x.clone() * x.clone() - Expression::one()) / (x.clone() - Expression::one());
+        let both = y
+            .limit(x.clone(), Expression::one(), Direction::Both, 2)
+            .unwrap();
+        assert_eq!(both.to_string(), "2");
+        let y = (Expression::one() - cos(x.clone())) / (x.clone() * x.clone());
+        let both = y.limit(x, Expression::zero(), Direction::Both, 2).unwrap();
+        assert_eq!(both.to_string(), "1/2");
+    }
+
+    #[test]
+    fn orders() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let y = x.clone().pow(2) / x.clone();
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 0)
+            .unwrap();
+        assert_eq!(l.to_string(), "0");
+        let y = (x.clone().pow(2) + x.clone().pow(3)) / (Expression::from(2) * x.clone().pow(2));
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Right, 0)
+            .unwrap();
+        assert_eq!(l.to_string(), "1/2");
+        // the order is in x - 1
+        let y = (x.clone() - Expression::one()).pow(3) * Expression::from(2);
+        let l = y
+            .limit(&vx, Expression::one(), Direction::Right, 1)
+            .unwrap();
+        let t = l.order().unwrap();
+        assert_eq!(t.coefficient.to_string(), "2");
+        assert_eq!(t.exponent, SmartNum::from(3));
     }
 }
@@ -0,0 +1,223 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::substitute::Substitute,
+    math_op::pow::Pow,
+};
+
+use super::{
+    limit_expression::{LimitExpression, NoValidLimitFound},
+    Direction, LimitTry,
+};
+
+/// Curve approaching the limit point, the i-th variable is replaced by
+/// the i-th coordinate of the point plus **offset[i]**, and the curve
+/// is followed for **parameter** -> 0+.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    pub parameter: Variable,
+    pub offset: Vec<Expression>,
+}
+
+impl Path {
+    pub fn new(parameter: Variable, offset: Vec<Expression>) -> Path {
+        Path { parameter, offset }
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let offset: Vec<String> = self.offset.iter().map(|o| o.to_string()).collect();
+        write!(
+            f,
+            "({}) for {} -> 0+",
+            offset.join(", "),
+            self.parameter.name
+        )
+    }
+}
+
+/// Outcome of testing a limit of several variables along paths.
+#[derive(Debug, Clone)]
+pub enum MultivariateLimit {
+    /// Every path that could be followed gives this value. It is only
+    /// a candidate, paths can't prove that the limit exists.
+    Common(LimitExpression),
+    /// Two paths with different limits, so the limit does not exist.
+    PathsDiffer(Box<(Path, LimitExpression)>, Box<(Path, LimitExpression)>),
+}
+
+impl Display for MultivariateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultivariateLimit::Common(l) => write!(f, "{}", l),
+            MultivariateLimit::PathsDiffer(p, q) => {
+                let ((p, a), (q, b)) = (p.as_ref(), q.as_ref());
+                write!(f, "does not exist ({} along {}, {} along {})", a, p, b, q)
+            }
+        }
+    }
+}
+
+/// Parameter name that is free in neither **expr** nor **of**.
+fn fresh_parameter(expr: &Expression, of: &[Variable]) -> Variable {
+    let taken = |name: &str| {
+        of.iter().any(|v| v.name == name) || expr.free_variables().iter().any(|v| v.name == name)
+    };
+    let mut name = String::from("t");
+    while taken(&name) {
+        name.push('\'');
+    }
+    Variable::new_variable(&name)
+}
+
+/// Lines through the point along each axis, the diagonals in both
+/// directions, and the parabolas where the first variable goes as t and
+/// the others as t ^ 2, or the other way round.
+pub fn default_paths(expr: &Expression, of: &[Variable]) -> Vec<Path> {
+    let parameter = fresh_parameter(expr, of);
+    let t = Expression::from(parameter.clone());
+    let n = of.len();
+    let mut offsets: Vec<Vec<Expression>> = vec![];
+    for sign in [t.clone(), -t.clone()] {
+        for i in 0..n {
+            let mut axis = vec![Expression::zero(); n];
+            axis[i] = sign.clone();
+            offsets.push(axis);
+        }
+        if n > 1 {
+            offsets.push(vec![sign.clone(); n]);
+            let mut anti = vec![sign.clone(); n];
+            anti[0] = -sign.clone();
+            offsets.push(anti);
+            let mut parabola = vec![sign.clone().pow(2); n];
+            parabola[0] = sign.clone();
+            offsets.push(parabola);
+            let mut parabola = vec![sign.clone(); n];
+            parabola[0] = sign.clone().pow(2);
+            offsets.push(parabola);
+        }
+    }
+    offsets
+        .into_iter()
+        .map(|offset| Path::new(parameter.clone(), offset))
+        .collect()
+}
+
+/// Limit of **expr** as **of** -> **to** along **path**.
+pub fn path_limit(
+    expr: &Expression,
+    of: &[Variable],
+    to: &[Expression],
+    path: &Path,
+    order_try: u64,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    if of.len() != to.len() || of.len() != path.offset.len() {
+        return Err(NoValidLimitFound {});
+    }
+    let mut z = expr.clone();
+    for ((v, p), o) in of.iter().zip(to).zip(&path.offset) {
+        let point = p.clone() + o.clone();
+        z = z.substitute(v, &point).map_err(|_| NoValidLimitFound {})?;
+    }
+    z.limit(
+        &path.parameter,
+        Expression::zero(),
+        Direction::Right,
+        order_try,
+    )
+}
+
+/// Limit of **expr** as the variables **of** tend to the point **to**,
+/// tested along **paths**, or along `default_paths` when none are given.
+/// Paths along which no limit is found are skipped, and it is an error
+/// if that leaves none.
+pub fn multivariate_limit(
+    expr: &Expression,
+    of: &[Variable],
+    to: &[Expression],
+    paths: &[Path],
+    order_try: u64,
+) -> Result<MultivariateLimit, NoValidLimitFound> {
+    let paths = if paths.is_empty() {
+        default_paths(expr, of)
+    } else {
+        paths.to_vec()
+    };
+    let mut first: Option<(Path, LimitExpression)> = None;
+    for path in paths {
+        let l = match path_limit(expr, of, to, &path, order_try) {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        match &first {
+            None => first = Some((path, l)),
+            Some((_, a)) if a.same_value(&l) => {}
+            Some(_) => {
+                return Ok(MultivariateLimit::PathsDiffer(
+                    Box::new(first.unwrap()),
+                    Box::new((path, l)),
+                ))
+            }
+        }
+    }
+    first
+        .map(|(_, l)| MultivariateLimit::Common(l))
+        .ok_or(NoValidLimitFound {})
+}
+
+#[cfg(test)]
+mod multivariate_limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::pow::Pow,
+    };
+
+    use super::{multivariate_limit, MultivariateLimit, Path};
+
+    #[test]
+    fn along_default_paths() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let of = [Variable::new_variable("x"), Variable::new_variable("y")];
+        let origin = [Expression::zero(), Expression::zero()];
+        let f = x.clone() * y.clone() / (x.clone().pow(2) + y.clone().pow(2));
+        let l = multivariate_limit(&f, &of, &origin, &[], 2).unwrap();
+        assert_eq!(
+            l.to_string(),
+            "does not exist (0 along (t, 0) for t -> 0+, 1/2 along (t, t) for t -> 0+)"
+        );
+        // lines agree, the parabola y = x^2 doesn't
+        let f = x.clone().pow(2) * y.clone() / (x.clone().pow(4) + y.clone().pow(2));
+        match multivariate_limit(&f, &of, &origin, &[], 2).unwrap() {
+            MultivariateLimit::PathsDiffer(p, q) => {
+                let (a, b) = (&p.1, &q.1);
+                assert_eq!(a.to_string(), "0");
+                assert_eq!(b.to_string(), "1/2");
+            }
+            l => panic!("expected differing paths, got {}", l),
+        }
+        let f = x.clone().pow(2) * y.clone() / (x.clone().pow(2) + y.clone().pow(2));
+        let l = multivariate_limit(&f, &of, &origin, &[], 2).unwrap();
+        assert_eq!(l.to_string(), "0");
+    }
+
+    #[test]
+    fn along_given_paths() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let s = Expression::new_variable("s");
+        let of = [Variable::new_variable("x"), Variable::new_variable("y")];
+        let at = [Expression::one(), Expression::from(2)];
+        let f = x.clone() + y.clone();
+        let path = Path::new(
+            Variable::new_variable("s"),
+            vec![s.clone().pow(3), -s.clone()],
+        );
+        let l = multivariate_limit(&f, &of, &at, &[path], 2).unwrap();
+        assert_eq!(l.to_string(), "3");
+    }
+}
@@ -1,14 +1,26 @@
 use crate::{
     ast::{op::operand::Variable, tree::Expression},
-    compute::limit::{
-        limit_expression::{LimitExpression, NoValidLimitFound},
-        LimitTry,
+    compute::{
+        derivative::Derivative,
+        evaluate::is_zero_expr,
+        limit::{
+            leading_term::leading_term,
+            limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+            Direction, LimitTry,
+        },
     },
+    smart_num::SmartNum,
 };
 
 pub(crate) trait AddLimitExprRule {
     type Output;
-    fn add_limit_expr_rule(self, of: &Variable, to: Expression, order_try: u64) -> Self::Output;
+    fn add_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
 }
 
 impl AddLimitExprRule for Expression {
@@ -18,19 +30,66 @@ impl AddLimitExprRule for Expression {
         mut self,
         of: &Variable,
         to: Expression,
+        direction: Direction,
         order_try: u64,
     ) -> Self::Output {
         let r_ = self.child.pop().unwrap();
         let l_ = self.child.pop().unwrap();
-        let r_limit = r_.clone().limit(of, to.clone(), order_try)?;
-        let l_limit = l_.clone().limit(of, to.clone(), order_try)?;
-        let first_try = l_limit + r_limit;
-        if first_try.is_ok() {
-            return Ok(first_try.unwrap());
-        } else {
-            // l + r = (l/r + 1)/(1/r)
-            let z = (l_ / r_.clone() + Expression::one()) / (Expression::one() / r_);
-            return z.limit(of, to, order_try);
+        let r_limit = r_.clone().limit(of, to.clone(), direction, order_try)?;
+        let l_limit = l_.clone().limit(of, to.clone(), direction, order_try)?;
+        let cancelled = matches!(l_limit, LimitExpression::Normal(_))
+            && matches!(r_limit, LimitExpression::Normal(_));
+        match l_limit + r_limit {
+            Ok(LimitExpression::Infinitesimal(Sign::Unknown)) if cancelled => {
+                if let Some(t) = leading_term(&(l_.clone() + r_.clone()), of) {
+                    return Ok(t.limit());
+                }
+                Ok(cancelled_order(l_ + r_, of, to, direction, order_try))
+            }
+            Ok(z) => Ok(z),
+            Err(_) => {
+                // infinity - infinity, try the leading terms first
+                if let Some(t) = leading_term(&(l_.clone() + r_.clone()), of) {
+                    return Ok(t.limit());
+                }
+                // l + r = (l/r + 1)/(1/r)
+                let z = (l_ / r_.clone() + Expression::one()) / (Expression::one() / r_);
+                z.limit(of, to, direction, order_try)
+            }
+        }
+    }
+}
+
+/// Limit of f near 0+ when f -> 0 through cancellation. Since f(0+) = 0,
+/// f' ~ c * x^e gives f ~ c / (e + 1) * x^(e + 1), and otherwise f at
+/// least has the sign of the limit of f'.
+fn cancelled_order(
+    f: Expression,
+    of: &Variable,
+    to: Expression,
+    direction: Direction,
+    order_try: u64,
+) -> LimitExpression {
+    let unknown = LimitExpression::Infinitesimal(Sign::Unknown);
+    if order_try == 0 {
+        return unknown;
+    }
+    let d = match f
+        .derivative(of)
+        .ok()
+        .and_then(|d| d.limit(of, to, direction, order_try - 1).ok())
+    {
+        Some(d) => d,
+        None => return unknown,
+    };
+    match d {
+        LimitExpression::Normal(c) if !is_zero_expr(&c) => {
+            LimitExpression::term(c, SmartNum::one())
+        }
+        LimitExpression::Term(t) if t.exponent.to_f64() > -1.0 => {
+            let e = t.exponent + SmartNum::one();
+            LimitExpression::term(t.coefficient / Expression::from(e), e)
         }
+        d => LimitExpression::Infinitesimal(d.sign()),
     }
 }
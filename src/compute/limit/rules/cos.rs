@@ -1,15 +1,25 @@
 use crate::{
     ast::{op::operand::Variable, tree::Expression},
     compute::{
-        limit::limit_expression::{LimitExpression, NoValidLimitFound},
+        evaluate::is_zero_expr,
+        limit::{
+            limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+            Direction, LimitTry,
+        },
         num_aggregate::NumAggregate,
-        substitute::Substitute,
     },
+    math_op::cos::cos,
 };
 
 pub(crate) trait CosLimitExprRule {
     type Output;
-    fn cos_limit_expr_rule(self, of: &Variable, to: Expression, order_try: u64) -> Self::Output;
+    fn cos_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
 }
 
 // TODO: cos(k * pi + pi / 2) = 0
@@ -17,16 +27,58 @@ pub(crate) trait CosLimitExprRule {
 impl CosLimitExprRule for Expression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
-    fn cos_limit_expr_rule(self, of: &Variable, to: Expression, _order_try: u64) -> Self::Output {
-        self.substitute(of, &to)
-            .map_err(|_| NoValidLimitFound {})
-            .map(|expr| {
-                let z = expr.num_aggregate();
-                if z.is_zero() {
-                    LimitExpression::Infinitesimal
+    fn cos_limit_expr_rule(
+        mut self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let sub = self.child.pop().unwrap();
+        match sub.limit(of, to, direction, order_try)?.without_order() {
+            LimitExpression::Infinitesimal(_) => Ok(LimitExpression::Normal(Expression::one())),
+            LimitExpression::Normal(u) => {
+                let z = cos(u).num_aggregate();
+                if is_zero_expr(&z) {
+                    Ok(LimitExpression::Infinitesimal(Sign::Unknown))
                 } else {
-                    LimitExpression::Normal(z)
+                    Ok(LimitExpression::Normal(z))
                 }
-            })
+            }
+            // cos(1/x) for x -> 0 keeps oscillating within [-1, 1]
+            u if u.is_infinite() => Ok(LimitExpression::BoundedFluctuation),
+            LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
+            _ => Err(NoValidLimitFound {}),
+        }
+    }
+}
+
+#[cfg(test)]
+mod cos_limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::{limit_expression::LimitExpression, Direction, LimitTarget, LimitTry},
+        math_op::cos::cos,
+    };
+
+    #[test]
+    fn cos_compositions() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let y = cos(x.clone() + Expression::one());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l, LimitExpression::Normal(cos(Expression::one())));
+        let y = cos(Expression::one() / x.clone());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l, LimitExpression::BoundedFluctuation);
+        let y = cos(x.clone());
+        let l = y
+            .limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l, LimitExpression::BoundedFluctuation);
     }
 }
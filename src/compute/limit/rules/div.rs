@@ -3,15 +3,23 @@ use crate::{
     compute::{
         derivative::Derivative,
         limit::{
+            leading_term::leading_term,
             limit_expression::{LimitExpression, NoValidLimitFound},
-            LimitTry,
+            series::{series_ratio, SERIES_MAX_ORDER},
+            Direction, LimitTry,
         },
     },
 };
 
 pub(crate) trait DivLimitExprRule {
     type Output;
-    fn div_limit_expr_rule(self, of: &Variable, to: Expression, order_try: u64) -> Self::Output;
+    fn div_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
 }
 
 impl DivLimitExprRule for Expression {
@@ -21,35 +29,56 @@ impl DivLimitExprRule for Expression {
         mut self,
         of: &Variable,
         to: Expression,
+        direction: Direction,
         order_try: u64,
     ) -> Self::Output {
         let mut r = self.child.pop().unwrap();
         let mut l = self.child.pop().unwrap();
+        let (l0, r0) = (l.clone(), r.clone());
         // l/r
         for _ in 0..=order_try {
-            let a = l.clone().limit(of, to.clone(), order_try)?;
-            let b = r.clone().limit(of, to.clone(), order_try)?;
-            let z = a / b;
+            // a side L'Hopital can't take the limit of may still have a series
+            let z = match (
+                l.clone().limit(of, to.clone(), direction, order_try),
+                r.clone().limit(of, to.clone(), direction, order_try),
+            ) {
+                (Ok(a), Ok(b)) => a / b,
+                _ => break,
+            };
             if z.is_ok() {
                 return Ok(z.unwrap());
             }
-            l = l.derivative(of).map_err(|_| NoValidLimitFound {})?;
-            r = r.derivative(of).map_err(|_| NoValidLimitFound {})?;
+            // 0/0 or infinity/infinity, compare the leading terms first
+            if let Some(t) = leading_term(&(l.clone() / r.clone()), of) {
+                return Ok(t.limit());
+            }
+            match (l.derivative(of), r.derivative(of)) {
+                (Ok(dl), Ok(dr)) => (l, r) = (dl, dr),
+                _ => break,
+            }
         }
-        return Err(NoValidLimitFound {});
+        // L'Hopital didn't get there, compare the Taylor series instead
+        series_ratio(&l0, &r0, of, SERIES_MAX_ORDER)
+            .map(|t| t.limit())
+            .ok_or(NoValidLimitFound {})
     }
 }
 
 #[cfg(test)]
 mod div_limit_tests {
-    use crate::{ast::tree::Expression, compute::limit::LimitTry, math_op::sin::sin};
+    use crate::{
+        ast::op::operand::Variable,
+        ast::tree::Expression,
+        compute::limit::{Direction, LimitTry},
+        math_op::{exp::exp, pow::Pow, sin::sin, unevaluated::definite_integral},
+    };
 
     #[test]
     fn sinx_by_x() {
         let x = Expression::new_variable("x");
         let y = sin(x.clone()) / x.clone();
         let expr_str = y.to_string();
-        let limit = y.limit(x, Expression::zero(), 1).unwrap();
+        let limit = y.limit(x, Expression::zero(), Direction::Both, 1).unwrap();
         println!("limit of {}, when x -> 0: {}", expr_str, limit);
         assert_eq!(limit.to_string(), "1");
     }
@@ -59,9 +88,17 @@ mod div_limit_tests {
         let x = Expression::new_variable("x");
         let y = sin(x.clone()) / (x.clone() * x.clone());
         let expr_str = y.to_string();
-        let limit = y.limit(x, Expression::zero(), 1).unwrap();
+        let limit = y
+            .clone()
+            .limit(x.clone(), Expression::zero(), Direction::Both, 1)
+            .unwrap();
         println!("limit of {}, when x -> 0: {}", expr_str, limit);
-        assert_eq!(limit.to_string(), "infinity");
+        assert_eq!(
+            limit.to_string(),
+            "does not exist (left -infinity, right +infinity)"
+        );
+        let limit = y.limit(x, Expression::zero(), Direction::Right, 1).unwrap();
+        assert_eq!(limit.to_string(), "+infinity");
     }
 
     #[test]
@@ -69,8 +106,42 @@ mod div_limit_tests {
         let x = Expression::new_variable("x");
         let y = sin(x.clone()) * sin(x.clone()) / x.clone();
         let expr_str = y.to_string();
-        let limit = y.limit(x, Expression::zero(), 1).unwrap();
+        let limit = y.limit(x, Expression::zero(), Direction::Both, 1).unwrap();
         println!("limit of {}, when x -> 0: {}", expr_str, limit);
         assert_eq!(limit.to_string(), "0");
     }
+
+    #[test]
+    fn series_fallback() {
+        let x = Expression::new_variable("x");
+        // needs three rounds of L'Hopital
+        let y = (sin(x.clone()) - x.clone()) / x.clone().pow(3);
+        let limit = y
+            .limit(x.clone(), Expression::zero(), Direction::Both, 0)
+            .unwrap();
+        assert_eq!(limit.to_string(), "-1/6");
+        let y = (exp(x.clone())
+            - Expression::one()
+            - x.clone()
+            - x.clone().pow(2) / Expression::from(2))
+            / (x.clone() * sin(x.clone()).pow(2));
+        let limit = y.limit(x, Expression::zero(), Direction::Right, 1).unwrap();
+        assert_eq!(limit.to_string(), "1/6");
+    }
+
+    #[test]
+    fn series_fallback_without_limits() {
+        let x = Expression::new_variable("x");
+        let t = Variable::new_variable("t");
+        // an integral has no limit rule, but a series by the Leibniz rule
+        let g = definite_integral(
+            exp(-Expression::from(t.clone()).pow(2)),
+            &t,
+            Expression::zero(),
+            x.clone(),
+        );
+        let y = (g - x.clone()) / x.clone().pow(3);
+        let limit = y.limit(x, Expression::zero(), Direction::Both, 1).unwrap();
+        assert_eq!(limit.to_string(), "-1/3");
+    }
 }
@@ -0,0 +1,80 @@
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{
+        limit::{
+            limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+            Direction, LimitTry,
+        },
+        num_aggregate::NumAggregate,
+    },
+    math_op::exp::exp,
+};
+
+pub(crate) trait ExpLimitExprRule {
+    type Output;
+    fn exp_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
+}
+
+impl ExpLimitExprRule for Expression {
+    type Output = Result<LimitExpression, NoValidLimitFound>;
+
+    fn exp_limit_expr_rule(
+        mut self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let sub = self.child.pop().unwrap();
+        match sub.limit(of, to, direction, order_try)?.without_order() {
+            LimitExpression::Infinitesimal(_) => Ok(LimitExpression::Normal(Expression::one())),
+            LimitExpression::Normal(u) => Ok(LimitExpression::Normal(exp(u).num_aggregate())),
+            LimitExpression::PlusInfinity => Ok(LimitExpression::PlusInfinity),
+            LimitExpression::MinusInfinity => Ok(LimitExpression::Infinitesimal(Sign::Positive)),
+            LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
+            _ => Err(NoValidLimitFound {}),
+        }
+    }
+}
+
+#[cfg(test)]
+mod exp_limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::{
+            limit_expression::{LimitExpression, Sign},
+            Direction, LimitTarget, LimitTry,
+        },
+        math_op::exp::exp,
+    };
+
+    #[test]
+    fn exp_at_infinity() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let y = exp(x.clone());
+        let l = y
+            .clone()
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 1);
+        assert_eq!(l.unwrap(), LimitExpression::PlusInfinity);
+        let l = y.limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 1);
+        assert_eq!(l.unwrap(), LimitExpression::Infinitesimal(Sign::Positive));
+        // exp(1/x) at 0 differs from both sides
+        let y = exp(Expression::one() / x.clone());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l.to_string(), "does not exist (left 0, right +infinity)");
+        let y = (exp(x.clone()) - Expression::one()) / x.clone();
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l.to_string(), "1");
+    }
+}
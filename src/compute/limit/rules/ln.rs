@@ -0,0 +1,85 @@
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{
+        evaluate::is_zero_expr,
+        limit::{
+            limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+            Direction, LimitTry,
+        },
+        num_aggregate::NumAggregate,
+    },
+    math_op::ln::ln,
+};
+
+pub(crate) trait LnLimitExprRule {
+    type Output;
+    fn ln_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
+}
+
+impl LnLimitExprRule for Expression {
+    type Output = Result<LimitExpression, NoValidLimitFound>;
+
+    fn ln_limit_expr_rule(
+        mut self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let sub = self.child.pop().unwrap();
+        match sub.limit(of, to, direction, order_try)?.without_order() {
+            // ln is only defined for positive arguments
+            LimitExpression::Infinitesimal(Sign::Negative) => Err(NoValidLimitFound {}),
+            LimitExpression::Infinitesimal(_) => Ok(LimitExpression::MinusInfinity),
+            LimitExpression::Normal(u) => {
+                if Sign::of(&u) == Sign::Negative || u.is_zero() {
+                    return Err(NoValidLimitFound {});
+                }
+                let z = ln(u).num_aggregate();
+                if is_zero_expr(&z) {
+                    Ok(LimitExpression::Infinitesimal(Sign::Unknown))
+                } else {
+                    Ok(LimitExpression::Normal(z))
+                }
+            }
+            LimitExpression::PlusInfinity => Ok(LimitExpression::PlusInfinity),
+            _ => Err(NoValidLimitFound {}),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ln_limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::{limit_expression::LimitExpression, Direction, LimitTarget, LimitTry},
+        math_op::ln::ln,
+    };
+
+    #[test]
+    fn ln_limits() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let l = ln(x.clone()).limit(&vx, Expression::zero(), Direction::Right, 1);
+        assert_eq!(l.unwrap(), LimitExpression::MinusInfinity);
+        let y = x.clone() * ln(x.clone());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Right, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "0");
+        let y = ln(x.clone()) / x.clone();
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "0");
+        let y = ln(x.clone()) / (x.clone() - Expression::one());
+        let l = y.limit(&vx, Expression::one(), Direction::Both, 2).unwrap();
+        assert_eq!(l.to_string(), "1");
+    }
+}
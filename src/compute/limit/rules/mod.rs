@@ -4,4 +4,7 @@ pub(crate) mod sub;
 pub(crate) mod mul;
 pub(crate) mod div;
 pub(crate) mod sin;
-pub(crate) mod cos;
\ No newline at end of file
+pub(crate) mod cos;
+pub(crate) mod exp;
+pub(crate) mod ln;
+pub(crate) mod pow;
\ No newline at end of file
@@ -0,0 +1,181 @@
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{
+        limit::{
+            limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+            Direction, LimitTry,
+        },
+        num_aggregate::NumAggregate,
+    },
+    math_op::{exp::exp, ln::ln, pow::Pow},
+    smart_num::{SmartNum, ToSmartNum},
+};
+
+pub(crate) trait PowLimitExprRule {
+    type Output;
+    fn pow_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
+}
+
+impl PowLimitExprRule for Expression {
+    type Output = Result<LimitExpression, NoValidLimitFound>;
+
+    fn pow_limit_expr_rule(
+        mut self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let r = self.child.pop().unwrap();
+        let l = self.child.pop().unwrap();
+        if !r.contains_variable(of) {
+            if let Ok(k) = (&r).to_smart_num() {
+                let base = l.clone().limit(of, to.clone(), direction, order_try)?;
+                return constant_power(base, *k);
+            }
+            if let LimitExpression::Normal(a) =
+                l.clone().limit(of, to.clone(), direction, order_try)?
+            {
+                if !a.is_zero() {
+                    return Ok(LimitExpression::Normal(a.pow(r).num_aggregate()));
+                }
+            }
+        }
+        // l^r = exp(r * ln(l)) covers 0^0, 1^infinity and infinity^0
+        exp(r * ln(l)).limit(of, to, direction, order_try)
+    }
+}
+
+/// Sign of u^k from the sign of u, powers that are not integers are
+/// only defined for positive u.
+fn power_sign(sign: Sign, k: &SmartNum) -> Result<Sign, NoValidLimitFound> {
+    match k.to_i64() {
+        Some(n) if n % 2 == 0 => Ok(Sign::Positive),
+        Some(_) => Ok(sign),
+        None if sign == Sign::Negative => Err(NoValidLimitFound {}),
+        None => Ok(Sign::Positive),
+    }
+}
+
+fn constant_power(
+    base: LimitExpression,
+    k: SmartNum,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    if k.is_zero() {
+        return Ok(LimitExpression::Normal(Expression::one()));
+    }
+    let positive = k.sign() > 0;
+    if let LimitExpression::Term(t) = &base {
+        if k.to_i64().is_some() || Sign::of(&t.coefficient) == Sign::Positive {
+            return Ok(LimitExpression::term(
+                t.coefficient.clone().pow(Expression::from(k)),
+                t.exponent * k,
+            ));
+        }
+    }
+    let base = base.without_order();
+    match base {
+        LimitExpression::Normal(a) if a.is_zero() => {
+            if positive {
+                Ok(LimitExpression::Normal(Expression::zero()))
+            } else {
+                Err(NoValidLimitFound {})
+            }
+        }
+        LimitExpression::Normal(a) => Ok(LimitExpression::Normal(
+            a.pow(Expression::from(k)).num_aggregate(),
+        )),
+        LimitExpression::Infinitesimal(s) => {
+            let sign = power_sign(s, &k)?;
+            if positive {
+                Ok(LimitExpression::Infinitesimal(sign))
+            } else {
+                Ok(LimitExpression::infinity(sign))
+            }
+        }
+        LimitExpression::PlusInfinity
+        | LimitExpression::MinusInfinity
+        | LimitExpression::Infinity => {
+            let sign = power_sign(base.sign(), &k)?;
+            if positive {
+                Ok(LimitExpression::infinity(sign))
+            } else {
+                Ok(LimitExpression::Infinitesimal(sign))
+            }
+        }
+        LimitExpression::BoundedFluctuation if positive => Ok(LimitExpression::BoundedFluctuation),
+        _ => Err(NoValidLimitFound {}),
+    }
+}
+
+#[cfg(test)]
+mod pow_limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::{limit_expression::LimitExpression, Direction, LimitTarget, LimitTry},
+        math_op::pow::Pow,
+    };
+
+    #[test]
+    fn indeterminate_powers() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let one = Expression::one();
+        // 1^infinity
+        let y = (one.clone() + one.clone() / x.clone()).pow(x.clone());
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 3)
+            .unwrap();
+        assert_eq!(l.to_string(), "e");
+        let y = (one.clone() + Expression::from(2) / x.clone()).pow(x.clone());
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 3)
+            .unwrap();
+        assert_eq!(l.to_string(), "exp2");
+        // 0^0
+        let y = x.clone().pow(x.clone());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Right, 3)
+            .unwrap();
+        assert_eq!(l.to_string(), "1");
+        // infinity^0
+        let y = x.clone().pow(one.clone() / x.clone());
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 3)
+            .unwrap();
+        assert_eq!(l.to_string(), "1");
+    }
+
+    #[test]
+    fn constant_exponents() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let y = (Expression::from(3) * x.clone().pow(2) + Expression::one())
+            / (x.clone().pow(2) - Expression::from(2));
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "3");
+        let y = Expression::one() / x.clone().pow(2);
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l, LimitExpression::PlusInfinity);
+        let y = x.clone().pow(3);
+        let l = y
+            .limit(&vx, LimitTarget::MinusInfinity, Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l, LimitExpression::MinusInfinity);
+        let y = (x.clone() + Expression::one()).pow(2);
+        let l = y
+            .limit(&vx, Expression::from(2), Direction::Both, 2)
+            .unwrap();
+        assert_eq!(l.to_string(), "9");
+    }
+}
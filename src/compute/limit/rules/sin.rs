@@ -1,15 +1,25 @@
 use crate::{
     ast::{op::operand::Variable, tree::Expression},
     compute::{
-        limit::limit_expression::{LimitExpression, NoValidLimitFound},
+        evaluate::is_zero_expr,
+        limit::{
+            limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+            Direction, LimitTry,
+        },
         num_aggregate::NumAggregate,
-        substitute::Substitute,
     },
+    math_op::sin::sin,
 };
 
 pub(crate) trait SinLimitExprRule {
     type Output;
-    fn sin_limit_expr_rule(self, of: &Variable, to: Expression, order_try: u64) -> Self::Output;
+    fn sin_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
 }
 
 // TODO: sin(k * pi) = 0
@@ -17,20 +27,65 @@ pub(crate) trait SinLimitExprRule {
 impl SinLimitExprRule for Expression {
     type Output = Result<LimitExpression, NoValidLimitFound>;
 
-    fn sin_limit_expr_rule(self, of: &Variable, to: Expression, _order_try: u64) -> Self::Output {
-        if to.is_zero() {
-            Ok(LimitExpression::Infinitesimal)
-        } else {
-            self.substitute(of, &to)
-                .map_err(|_| NoValidLimitFound {})
-                .map(|expr| {
-                    let z = expr.num_aggregate();
-                    if z.is_zero() {
-                        LimitExpression::Infinitesimal
-                    } else {
-                        LimitExpression::Normal(z)
-                    }
-                })
+    fn sin_limit_expr_rule(
+        mut self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output {
+        let sub = self.child.pop().unwrap();
+        match sub.limit(of, to, direction, order_try)? {
+            // sin(u) ~ u for u -> 0
+            u if u.is_zero() && !matches!(u, LimitExpression::Normal(_)) => Ok(u),
+            LimitExpression::Normal(u) => {
+                let z = sin(u).num_aggregate();
+                if is_zero_expr(&z) {
+                    Ok(LimitExpression::Infinitesimal(Sign::Unknown))
+                } else {
+                    Ok(LimitExpression::Normal(z))
+                }
+            }
+            // sin(1/x) for x -> 0 keeps oscillating within [-1, 1]
+            u if u.is_infinite() => Ok(LimitExpression::BoundedFluctuation),
+            LimitExpression::BoundedFluctuation => Ok(LimitExpression::BoundedFluctuation),
+            _ => Err(NoValidLimitFound {}),
         }
     }
 }
+
+#[cfg(test)]
+mod sin_limit_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::{limit_expression::LimitExpression, Direction, LimitTarget, LimitTry},
+        math_op::sin::sin,
+    };
+
+    #[test]
+    fn sin_compositions() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let y = sin(x.clone() + Expression::one());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l, LimitExpression::Normal(sin(Expression::one())));
+        let y = sin(Expression::one() / x.clone());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l, LimitExpression::BoundedFluctuation);
+        // bounded times infinitesimal
+        let y = x.clone() * sin(Expression::one() / x.clone());
+        let l = y
+            .limit(&vx, Expression::zero(), Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l.to_string(), "0");
+        let y = sin(x.clone()) / x.clone();
+        let l = y
+            .limit(&vx, LimitTarget::PlusInfinity, Direction::Both, 1)
+            .unwrap();
+        assert_eq!(l.to_string(), "0");
+    }
+}
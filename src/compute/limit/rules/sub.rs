@@ -2,13 +2,19 @@ use crate::{
     ast::{op::operand::Variable, tree::Expression},
     compute::limit::{
         limit_expression::{LimitExpression, NoValidLimitFound},
-        LimitTry,
+        Direction, LimitTry,
     },
 };
 
 pub(crate) trait SubLimitExprRule {
     type Output;
-    fn sub_limit_expr_rule(self, of: &Variable, to: Expression, order_try: u64) -> Self::Output;
+    fn sub_limit_expr_rule(
+        self,
+        of: &Variable,
+        to: Expression,
+        direction: Direction,
+        order_try: u64,
+    ) -> Self::Output;
 }
 
 impl SubLimitExprRule for Expression {
@@ -18,10 +24,11 @@ impl SubLimitExprRule for Expression {
         mut self,
         of: &Variable,
         to: Expression,
+        direction: Direction,
         order_try: u64,
     ) -> Self::Output {
         let r = self.child.pop().unwrap();
         let l = self.child.pop().unwrap();
-        (l + (-r)).limit(of, to, order_try)
+        (l + (-r)).limit(of, to, direction, order_try)
     }
 }
@@ -0,0 +1,362 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{
+        op::{
+            operand::{AstOperand, Variable},
+            operator::OperatorType,
+        },
+        tree::{AstNode, Expression},
+    },
+    compute::{
+        derivative::{cache::DerivativeCache, Derivative},
+        evaluate::{is_zero_expr, Evaluate},
+        num_aggregate::NumAggregate,
+        substitute::Substitute,
+    },
+    math_op::pow::Pow,
+};
+
+use super::{
+    limit_expression::{LimitExpression, NoValidLimitFound, Sign},
+    series::SERIES_MAX_ORDER,
+    Direction, LimitTarget, LimitTry,
+};
+
+/// Rounds of Stolz-Cesaro tried on nested quotients.
+const STOLZ_DEPTH: u64 = 3;
+
+/*
+ * Limits of sequences a_n as the integer n -> +infinity.
+ *
+ * Sequences without factorials, floors or sums up to n are smooth in
+ * n and left to the continuous limit at +infinity. The others are tried
+ * in turn with
+ * - floor(u) squeezed between u - 1 and u,
+ * - the ratio test, a_n -> 0 when |a_(n+1) / a_n| tends to less than 1
+ *   and |a_n| -> +infinity when it tends to more than 1, the sign being
+ *   the one a_n eventually keeps as read off its factors,
+ * - Stolz-Cesaro, a_n / b_n has the limit of
+ *   (a_(n+1) - a_n) / (b_(n+1) - b_n) when b_n increases to +infinity.
+*/
+
+/// Limit of **expr** as the integer **n** -> +infinity.
+pub fn sequence_limit(
+    expr: &Expression,
+    n: &Variable,
+    order_try: u64,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    limit_with_depth(expr, n, order_try, STOLZ_DEPTH)
+}
+
+fn limit_with_depth(
+    expr: &Expression,
+    n: &Variable,
+    order_try: u64,
+    depth: u64,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    if !is_discrete(expr, n) {
+        return continuous_limit(expr, n, order_try);
+    }
+    if let Some(l) = squeeze_floor(expr, n, order_try, depth) {
+        return Ok(l);
+    }
+    if let Some(l) = ratio_test(expr, n, order_try) {
+        return Ok(l);
+    }
+    if depth == 0 {
+        return Err(NoValidLimitFound {});
+    }
+    stolz_cesaro(expr, n, order_try, depth - 1).ok_or(NoValidLimitFound {})
+}
+
+fn continuous_limit(
+    expr: &Expression,
+    n: &Variable,
+    order_try: u64,
+) -> Result<LimitExpression, NoValidLimitFound> {
+    expr.clone()
+        .limit(n, LimitTarget::PlusInfinity, Direction::Both, order_try)
+}
+
+fn operator_of(expr: &Expression) -> Option<&OperatorType> {
+    match &expr.root {
+        AstNode::Operator(op) => Some(&op.descriptor),
+        AstNode::Operand(_) => None,
+    }
+}
+
+/// Whether **expr** has a factorial, floor or sum depending on **n**.
+fn is_discrete(expr: &Expression, n: &Variable) -> bool {
+    if !expr.contains_variable(n) {
+        return false;
+    }
+    match operator_of(expr) {
+        Some(OperatorType::Factorial | OperatorType::Floor | OperatorType::Sum) => true,
+        _ => expr.child.iter().any(|c| is_discrete(c, n)),
+    }
+}
+
+/// **expr** with n := n + 1.
+fn shift(expr: &Expression, n: &Variable) -> Option<Expression> {
+    let next = Expression::from(n.clone()) + Expression::one();
+    expr.clone().substitute(n, &next).ok()
+}
+
+/// The integer k when **expr** is k * n + c.
+fn linear_slope(expr: &Expression, n: &Variable) -> Option<i64> {
+    let slope = expr.clone().derivative(n).ok()?.num_aggregate();
+    if !slope.clone().derivative(n).ok()?.num_aggregate().is_zero() {
+        return None;
+    }
+    match &slope.root {
+        AstNode::Operand(AstOperand::Num(k)) => k.to_i64(),
+        _ => None,
+    }
+}
+
+/// a_(n+1) / a_n, with the factorials cancelled out.
+fn ratio(expr: &Expression, n: &Variable) -> Option<Expression> {
+    if !expr.contains_variable(n) {
+        return Some(Expression::one());
+    }
+    let child = &expr.child;
+    let r = match operator_of(expr) {
+        Some(OperatorType::Neg) => ratio(&child[0], n)?,
+        Some(OperatorType::Mul) => ratio(&child[0], n)? * ratio(&child[1], n)?,
+        Some(OperatorType::Div) => ratio(&child[0], n)? / ratio(&child[1], n)?,
+        Some(OperatorType::Pow) if !child[1].contains_variable(n) => {
+            ratio(&child[0], n)?.pow(child[1].clone())
+        }
+        Some(OperatorType::Pow) => {
+            // u(n+1)^v(n+1) / u^v = (u(n+1) / u)^v * u(n+1)^(v(n+1) - v)
+            let (u, v) = (&child[0], &child[1]);
+            let dv = difference(v, n)?;
+            if u.contains_variable(n) {
+                ratio(u, n)?.pow(v.clone()) * shift(u, n)?.pow(dv)
+            } else {
+                u.clone().pow(dv)
+            }
+        }
+        Some(OperatorType::Factorial) => {
+            // (u + k)! / u! = (u + 1) * ... * (u + k)
+            let k = linear_slope(&child[0], n).filter(|k| *k > 0)?;
+            (1..=k)
+                .map(|j| child[0].clone() + Expression::from(j))
+                .reduce(|a, b| a * b)?
+        }
+        _ if is_discrete(expr, n) => return None,
+        // 1 + 1/n rather than (n + 1) / n, the limit engine can't cancel that
+        _ => Expression::one() + difference(expr, n)? / expr.clone(),
+    };
+    Some(r.num_aggregate())
+}
+
+/// a_(n+1) - a_n, with sums up to n reduced to their last term.
+fn difference(expr: &Expression, n: &Variable) -> Option<Expression> {
+    if !expr.contains_variable(n) {
+        return Some(Expression::zero());
+    }
+    let child = &expr.child;
+    let d = match operator_of(expr) {
+        Some(OperatorType::Neg) => -difference(&child[0], n)?,
+        Some(OperatorType::Add) => difference(&child[0], n)? + difference(&child[1], n)?,
+        Some(OperatorType::Sub) => difference(&child[0], n)? - difference(&child[1], n)?,
+        Some(OperatorType::Sum) => {
+            // only the upper bound may move, by one each step
+            let (k, lower, upper) = (expr.bound_variable()?, &child[2], &child[3]);
+            if lower.contains_variable(n) || linear_slope(upper, n) != Some(1) {
+                return None;
+            }
+            let last = shift(upper, n)?;
+            child[0].clone().substitute(k, &last).ok()?
+        }
+        _ if is_discrete(expr, n) => expr.clone() * (ratio(expr, n)? - Expression::one()),
+        _ => polynomial_difference(expr, n).or_else(|| Some(shift(expr, n)? - expr.clone()))?,
+    };
+    Some(d.num_aggregate())
+}
+
+/// p(n+1) - p(n) as the sum of p^(k)(n) / k!, which is exact when
+/// **expr** is a polynomial in **n** of degree below `SERIES_MAX_ORDER`
+/// and saves the limit engine from cancelling (n + 1)^2 - n^2 itself.
+fn polynomial_difference(expr: &Expression, n: &Variable) -> Option<Expression> {
+    let mut cache = DerivativeCache::new();
+    let mut d = expr.clone();
+    let mut factorial = 1;
+    let mut result = Expression::zero();
+    for k in 1..=SERIES_MAX_ORDER as i64 {
+        d = cache.derivative(&d, n).ok()?.num_aggregate();
+        if d.is_zero() {
+            return Some(result);
+        }
+        factorial *= k;
+        result = result + d.clone() / Expression::from(factorial);
+    }
+    None
+}
+
+/// Replaces every floor(u) with **bound**(u).
+fn replace_floor(expr: &Expression, bound: &dyn Fn(Expression) -> Expression) -> Expression {
+    let mut z = expr.clone();
+    z.child = z.child.iter().map(|c| replace_floor(c, bound)).collect();
+    match operator_of(&z) {
+        Some(OperatorType::Floor) => bound(z.child.pop().unwrap()),
+        _ => z,
+    }
+}
+
+/// u - 1 < floor(u) <= u, when both bounds tend to the same value so
+/// does **expr**. Assumes **expr** is monotone in each floor.
+fn squeeze_floor(
+    expr: &Expression,
+    n: &Variable,
+    order_try: u64,
+    depth: u64,
+) -> Option<LimitExpression> {
+    let lower = replace_floor(expr, &|u| u - Expression::one());
+    let upper = replace_floor(expr, &|u| u);
+    if lower == upper {
+        return None;
+    }
+    let a = limit_with_depth(&lower, n, order_try, depth).ok()?;
+    let b = limit_with_depth(&upper, n, order_try, depth).ok()?;
+    if a.same_value(&b) {
+        Some(b)
+    } else {
+        None
+    }
+}
+
+fn stolz_cesaro(
+    expr: &Expression,
+    n: &Variable,
+    order_try: u64,
+    depth: u64,
+) -> Option<LimitExpression> {
+    if operator_of(expr) != Some(&OperatorType::Div) {
+        return None;
+    }
+    let (a, b) = (&expr.child[0], &expr.child[1]);
+    if limit_with_depth(b, n, order_try, depth).ok()? != LimitExpression::PlusInfinity {
+        return None;
+    }
+    let db = difference(b, n)?;
+    if limit_with_depth(&db, n, order_try, depth).ok()?.sign() != Sign::Positive {
+        return None;
+    }
+    let da = difference(a, n)?;
+    limit_with_depth(&(da / db), n, order_try, depth).ok()
+}
+
+/// Sign a_n eventually keeps, from the factors of **expr**: factorials
+/// are positive and smooth factors have the sign of their limit.
+fn eventual_sign(expr: &Expression, n: &Variable, order_try: u64) -> Sign {
+    if !expr.contains_variable(n) {
+        return Sign::of(expr);
+    }
+    let child = &expr.child;
+    match operator_of(expr) {
+        Some(OperatorType::Neg) => -eventual_sign(&child[0], n, order_try),
+        Some(OperatorType::Mul | OperatorType::Div) => {
+            eventual_sign(&child[0], n, order_try) * eventual_sign(&child[1], n, order_try)
+        }
+        Some(OperatorType::Pow) => match eventual_sign(&child[0], n, order_try) {
+            Sign::Positive => Sign::Positive,
+            _ => Sign::Unknown,
+        },
+        Some(OperatorType::Factorial) => Sign::Positive,
+        _ if is_discrete(expr, n) => Sign::Unknown,
+        _ => continuous_limit(expr, n, order_try)
+            .map(|l| l.sign())
+            .unwrap_or(Sign::Unknown),
+    }
+}
+
+fn ratio_test(expr: &Expression, n: &Variable, order_try: u64) -> Option<LimitExpression> {
+    let r = ratio(expr, n)?;
+    let l = continuous_limit(&r, n, order_try).ok()?.without_order();
+    let l = match l {
+        l if l.is_zero() => 0.0,
+        LimitExpression::Normal(l) => l.evaluate(&HashMap::new()).ok()?,
+        LimitExpression::PlusInfinity => f64::INFINITY,
+        _ => return None,
+    };
+    let sign = eventual_sign(expr, n, order_try);
+    if l.abs() < 1.0 {
+        return Some(LimitExpression::Infinitesimal(sign));
+    }
+    if l > 1.0 && sign != Sign::Unknown && !is_zero_expr(&r) {
+        return Some(LimitExpression::infinity(sign));
+    }
+    None
+}
+
+#[cfg(test)]
+mod sequence_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::limit::limit_expression::LimitExpression,
+        math_op::{
+            discrete::{factorial, floor},
+            pow::Pow,
+            unevaluated::sum,
+        },
+    };
+
+    use super::sequence_limit;
+
+    #[test]
+    fn smooth_sequences() {
+        let n = Expression::new_variable("n");
+        let vn = Variable::new_variable("n");
+        let a = (Expression::one() + Expression::one() / n.clone()).pow(n.clone());
+        assert_eq!(sequence_limit(&a, &vn, 2).unwrap().to_string(), "e");
+    }
+
+    #[test]
+    fn ratio_test() {
+        let n = Expression::new_variable("n");
+        let vn = Variable::new_variable("n");
+        let a = Expression::from(2).pow(n.clone()) / factorial(n.clone());
+        assert_eq!(sequence_limit(&a, &vn, 2).unwrap().to_string(), "0");
+        let a = factorial(n.clone()) / Expression::from(3).pow(n.clone());
+        assert_eq!(
+            sequence_limit(&a, &vn, 2).unwrap(),
+            LimitExpression::PlusInfinity
+        );
+        let a = factorial(n.clone()) / n.clone().pow(n.clone());
+        assert_eq!(sequence_limit(&a, &vn, 2).unwrap().to_string(), "0");
+        // positive for small n, the sign comes from the limit of 100 - n
+        let a = (Expression::from(100) - n.clone()) * factorial(n.clone());
+        assert_eq!(
+            sequence_limit(&a, &vn, 2).unwrap(),
+            LimitExpression::MinusInfinity
+        );
+    }
+
+    #[test]
+    fn stolz_cesaro() {
+        let n = Expression::new_variable("n");
+        let vn = Variable::new_variable("n");
+        let k = Variable::new_variable("k");
+        let s = sum(
+            Expression::from(k.clone()),
+            &k,
+            Expression::one(),
+            n.clone(),
+        );
+        let a = s / n.clone().pow(2);
+        assert_eq!(sequence_limit(&a, &vn, 2).unwrap().to_string(), "1/2");
+    }
+
+    #[test]
+    fn floors() {
+        let n = Expression::new_variable("n");
+        let vn = Variable::new_variable("n");
+        let a = floor(n.clone() / Expression::from(2)) / n.clone();
+        assert_eq!(sequence_limit(&a, &vn, 2).unwrap().to_string(), "1/2");
+    }
+}
@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{
+        evaluate::{is_zero_expr, Evaluate},
+        num_aggregate::NumAggregate,
+        taylor_expansion::partial_expansion::TaylorExpansion,
+    },
+    smart_num::SmartNum,
+};
+
+use super::leading_term::LeadingTerm;
+
+/// Highest Taylor order tried before giving up on a leading term.
+pub const SERIES_MAX_ORDER: u64 = 8;
+
+/// A Taylor coefficient is only trusted when it has a finite value.
+fn is_finite_expr(expr: &Expression) -> bool {
+    let bindings: HashMap<String, f64> = expr
+        .free_variables()
+        .iter()
+        .enumerate()
+        .map(|(idx, v)| (v.name.clone(), 0.613 + 0.173 * idx as f64))
+        .collect();
+    // bare numbers aren't checked by evaluate, e.g. 0/0 left over from x := 0
+    expr.evaluate(&bindings).is_ok_and(|v| v.is_finite())
+}
+
+/// Leading term of **expr** as **of** -> 0 from its Taylor expansion at 0,
+/// the first coefficient that doesn't vanish up to **max_order**. None when
+/// **expr** is not analytic at 0 or vanishes to a higher order.
+///
+/// Derivatives grow quickly, so the order is doubled until a non-zero
+/// coefficient shows up instead of expanding to **max_order** at once.
+pub fn series_leading_term(
+    expr: &Expression,
+    of: &Variable,
+    max_order: u64,
+) -> Option<LeadingTerm> {
+    let mut order = 0;
+    loop {
+        let expansion = expr
+            .clone()
+            .taylor_expansion(of, &Expression::zero(), order)
+            .ok()?;
+        for (k, c) in expansion.coefficient.into_iter().enumerate() {
+            let c = c.num_aggregate();
+            if !is_finite_expr(&c) {
+                return None;
+            }
+            if !is_zero_expr(&c) {
+                return Some(LeadingTerm {
+                    coefficient: c,
+                    exponent: SmartNum::from(k as i64),
+                });
+            }
+        }
+        if order >= max_order {
+            return None;
+        }
+        order = (order * 2).clamp(1, max_order);
+    }
+}
+
+/// Leading term of **l** / **r** as **of** -> 0 from the series of both.
+pub fn series_ratio(
+    l: &Expression,
+    r: &Expression,
+    of: &Variable,
+    max_order: u64,
+) -> Option<LeadingTerm> {
+    let (l, r) = (
+        series_leading_term(l, of, max_order)?,
+        series_leading_term(r, of, max_order)?,
+    );
+    Some(LeadingTerm {
+        coefficient: (l.coefficient / r.coefficient).num_aggregate(),
+        exponent: l.exponent - r.exponent,
+    })
+}
+
+#[cfg(test)]
+mod series_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{cos::cos, ln::ln, pow::Pow, sin::sin},
+        smart_num::SmartNum,
+    };
+
+    use super::{series_leading_term, series_ratio, SERIES_MAX_ORDER};
+
+    #[test]
+    fn leading_terms() {
+        let x = Expression::new_variable("x");
+        let of = Variable::new_variable("x");
+        let t = series_leading_term(&(sin(x.clone()) - x.clone()), &of, SERIES_MAX_ORDER).unwrap();
+        assert_eq!(t.coefficient.to_string(), "-1/6");
+        assert_eq!(t.exponent, SmartNum::from(3));
+        let t = series_ratio(
+            &(Expression::one() - cos(x.clone())),
+            &x.clone().pow(2),
+            &of,
+            SERIES_MAX_ORDER,
+        )
+        .unwrap();
+        assert_eq!(t.coefficient.to_string(), "1/2");
+        assert_eq!(t.exponent, SmartNum::zero());
+        // ln is not analytic at 0
+        assert!(series_leading_term(&ln(x), &of, SERIES_MAX_ORDER).is_none());
+    }
+}
@@ -0,0 +1,512 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{
+        evaluate::{is_zero_expr, Evaluate},
+        num_aggregate::NumAggregate,
+    },
+    math_op::pow::Pow,
+    smart_num::ToSmartNum,
+};
+
+use super::{
+    roots::{exact_roots, numeric_roots},
+    Matrix, MatrixError,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct NumericEigenvalue {
+    pub re: f64,
+    pub im: f64,
+}
+
+#[derive(Debug, Clone)]
+pub enum Eigenvalues {
+    /// Closed form values, repeated by algebraic multiplicity.
+    Exact(Vec<Expression>),
+    /// Floating point values, used when the characteristic polynomial
+    /// cannot be solved exactly but every coefficient evaluates to a number.
+    Numeric(Vec<NumericEigenvalue>),
+}
+
+#[derive(Debug, Clone)]
+pub struct EigenSpace {
+    pub value: Expression,
+    pub multiplicity: usize,
+    /// Column vectors spanning the eigenspace.
+    pub basis: Vec<Matrix>,
+}
+
+impl Matrix {
+    /// Coefficients c_0, ..., c_n of det(λI - A), lowest degree first,
+    /// computed with the Faddeev-LeVerrier recurrence.
+    pub fn characteristic_coefficients(&self) -> Result<Vec<Expression>, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError {
+                reason: "characteristic polynomial of a non-square matrix",
+            });
+        }
+        let n = self.rows;
+        let mut c = vec![Expression::zero(); n + 1];
+        c[n] = Expression::one();
+        let mut m = Matrix::zeros(n, n);
+        for k in 1..=n {
+            // M_k = A M_{k-1} + c_{n-k+1} I, c_{n-k} = -tr(A M_k) / k
+            m = ((self.clone() * m)? + Matrix::identity(n).scale(&c[n - k + 1]))?;
+            let t = (self.clone() * m.clone())?.trace()?;
+            c[n - k] = (-t / Expression::from(k as i64)).num_aggregate();
+        }
+        Ok(c)
+    }
+
+    pub fn characteristic_polynomial(&self, of: &Variable) -> Result<Expression, MatrixError> {
+        let c = self.characteristic_coefficients()?;
+        let x = Expression::from(of.clone());
+        let mut p = Expression::zero();
+        for (k, c_k) in c.into_iter().enumerate() {
+            if c_k.is_zero() {
+                continue;
+            }
+            let term = match k {
+                0 => c_k,
+                1 => c_k * x.clone(),
+                _ => c_k * x.clone().pow(k as i64),
+            };
+            p = (p + term).num_aggregate();
+        }
+        Ok(p)
+    }
+
+    /// The principal submatrix of rows and columns **from**..**to**.
+    fn principal_block(&self, from: usize, to: usize) -> Matrix {
+        let mut m = Matrix::zeros(to - from, to - from);
+        for i in from..to {
+            for j in from..to {
+                m.set(i - from, j - from, self.get(i, j).clone());
+            }
+        }
+        m
+    }
+
+    /// First k such that the matrix is block triangular with leading
+    /// block k x k, i.e. the k x (n - k) or (n - k) x k corner vanishes.
+    fn block_split(&self) -> Option<usize> {
+        let n = self.rows;
+        let zero = |rows: std::ops::Range<usize>, cols: std::ops::Range<usize>| {
+            rows.clone()
+                .all(|i| cols.clone().all(|j| is_zero_expr(self.get(i, j))))
+        };
+        (1..n).find(|&k| zero(k..n, 0..k) || zero(0..k, k..n))
+    }
+
+    /// Eigenvalues in closed form, the diagonal blocks of a block
+    /// triangular matrix are solved one at a time, e.g. every diagonal
+    /// entry of a triangular matrix.
+    fn exact_eigenvalues(&self) -> Result<Option<Vec<Expression>>, MatrixError> {
+        if self.rows == 1 {
+            return Ok(Some(vec![self.get(0, 0).clone().num_aggregate()]));
+        }
+        let Some(k) = self.block_split() else {
+            return Ok(exact_roots(&self.characteristic_coefficients()?));
+        };
+        let (Some(mut a), Some(b)) = (
+            self.principal_block(0, k).exact_eigenvalues()?,
+            self.principal_block(k, self.rows).exact_eigenvalues()?,
+        ) else {
+            return Ok(None);
+        };
+        a.extend(b);
+        Ok(Some(a))
+    }
+
+    /// Exact eigenvalues are found for the diagonal blocks of a block
+    /// triangular matrix whose characteristic polynomials are at most
+    /// quadratic, or have rational coefficients and at most a quadratic
+    /// or a quartic solvable by Ferrari's method left once the rational
+    /// roots are taken out. An irreducible cubic is never solved exactly,
+    /// see **exact_roots**. Otherwise a characteristic polynomial whose
+    /// coefficients evaluate to numbers gets floating point values, even
+    /// when the entries aren't plain numbers, and a symbolic one an error.
+    pub fn eigenvalues(&self) -> Result<Eigenvalues, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError {
+                reason: "eigenvalues of a non-square matrix",
+            });
+        }
+        if let Some(mut values) = self.exact_eigenvalues()? {
+            let key = |v: &Expression| v.evaluate(&HashMap::new());
+            if values.iter().all(|v| key(v).is_ok()) {
+                values.sort_by(|a, b| key(a).unwrap().total_cmp(&key(b).unwrap()));
+            }
+            return Ok(Eigenvalues::Exact(values));
+        }
+        let c = self.characteristic_coefficients()?;
+        let c: Vec<f64> = c
+            .iter()
+            .map(|v| v.evaluate(&HashMap::new()))
+            .collect::<Result<_, _>>()
+            .map_err(|_| MatrixError {
+                reason: "no closed form eigenvalues, a symbolic characteristic \
+                    polynomial is only solved up to quadratic factors",
+            })?;
+        Ok(Eigenvalues::Numeric(
+            numeric_roots(&c)
+                .into_iter()
+                .map(|(re, im)| NumericEigenvalue { re, im })
+                .collect(),
+        ))
+    }
+
+    /// Eigenvalues grouped with a basis of their eigenspaces. Complex
+    /// numeric eigenvalues have no real eigenvectors and are skipped.
+    pub fn eigenvectors(&self) -> Result<Vec<EigenSpace>, MatrixError> {
+        let (values, eps) = match self.eigenvalues()? {
+            Eigenvalues::Exact(values) => (values, 0.0),
+            Eigenvalues::Numeric(values) => {
+                let scale = self.data.iter().fold(1.0_f64, |s, v| {
+                    s.max(v.to_smart_num().map_or(0.0, |v| v.to_f64().abs()))
+                });
+                let real = values
+                    .into_iter()
+                    .filter(|v| v.im == 0.0)
+                    .map(|v| Expression::from(v.re))
+                    .collect();
+                (real, 1e-6 * scale)
+            }
+        };
+        let mut spaces: Vec<EigenSpace> = vec![];
+        for value in values {
+            if let Some(space) = spaces
+                .iter_mut()
+                .find(|s| is_zero_expr(&(s.value.clone() - value.clone()).num_aggregate()))
+            {
+                space.multiplicity += 1;
+                continue;
+            }
+            let shifted = (self.clone() - Matrix::identity(self.rows).scale(&value))?;
+            spaces.push(EigenSpace {
+                value,
+                multiplicity: 1,
+                basis: shifted.nullspace_with(eps),
+            });
+        }
+        Ok(spaces)
+    }
+
+    /// Column vectors spanning the nullspace.
+    pub fn nullspace(&self) -> Vec<Matrix> {
+        self.nullspace_with(0.0)
+    }
+
+    /// Gauss-Jordan elimination, floating point entries smaller than
+    /// **eps** count as zero.
+    fn nullspace_with(&self, eps: f64) -> Vec<Matrix> {
+        let is_zero = |v: &Expression| match v.to_smart_num() {
+            Ok(num) if !num.is_zero() && eps > 0.0 => num.to_f64().abs() < eps,
+            _ => is_zero_expr(v),
+        };
+        let mut m = self.clone();
+        let mut pivots: Vec<usize> = vec![];
+        for col in 0..m.cols {
+            let r = pivots.len();
+            let Some(p) = (r..m.rows).find(|&i| !is_zero(m.get(i, col))) else {
+                continue;
+            };
+            for j in 0..m.cols {
+                m.data.swap(r * m.cols + j, p * m.cols + j);
+            }
+            let pivot = m.get(r, col).clone();
+            for j in 0..m.cols {
+                let v = (m.get(r, j).clone() / pivot.clone()).num_aggregate();
+                m.set(r, j, v);
+            }
+            m.set(r, col, Expression::one());
+            for i in 0..m.rows {
+                let factor = m.get(i, col).clone();
+                if i == r || is_zero(&factor) {
+                    continue;
+                }
+                for j in 0..m.cols {
+                    let v = (m.get(i, j).clone() - factor.clone() * m.get(r, j).clone())
+                        .num_aggregate();
+                    m.set(i, j, v);
+                }
+                m.set(i, col, Expression::zero());
+            }
+            pivots.push(col);
+        }
+        let mut basis = vec![];
+        for free in (0..m.cols).filter(|c| !pivots.contains(c)) {
+            let mut v = vec![Expression::zero(); m.cols];
+            v[free] = Expression::one();
+            for (r, &p) in pivots.iter().enumerate() {
+                v[p] = (-m.get(r, free).clone()).num_aggregate();
+            }
+            basis.push(Matrix::from_column(v));
+        }
+        basis
+    }
+}
+
+#[cfg(test)]
+mod eigen_tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::{
+            evaluate::{is_zero_expr, Evaluate},
+            num_aggregate::NumAggregate,
+        },
+        math_op::pow::Pow,
+    };
+
+    use super::{super::Matrix, Eigenvalues};
+
+    fn matrix(rows: Vec<Vec<i64>>) -> Matrix {
+        Matrix::from_rows(
+            rows.into_iter()
+                .map(|r| r.into_iter().map(Expression::from).collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn exact(m: &Matrix) -> Vec<String> {
+        match m.eigenvalues().unwrap() {
+            Eigenvalues::Exact(v) => v.iter().map(|v| v.to_string()).collect(),
+            Eigenvalues::Numeric(_) => panic!("expected exact eigenvalues"),
+        }
+    }
+
+    #[test]
+    fn characteristic_polynomial() {
+        let m = matrix(vec![vec![1, 2], vec![3, 4]]);
+        let p = m
+            .characteristic_polynomial(&Variable::new_variable("t"))
+            .unwrap();
+        assert_eq!(p.to_string(), "-2 + -5 * t + t ^ 2");
+    }
+
+    #[test]
+    fn rational_eigenvalues() {
+        let m = matrix(vec![vec![2, 0, 0], vec![0, 3, 4], vec![0, 4, 9]]);
+        assert_eq!(exact(&m), vec!["1", "2", "11"]);
+        let m = matrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![-4, 0, 5, 0],
+        ]);
+        assert_eq!(exact(&m), vec!["-2", "-1", "1", "2"]);
+    }
+
+    #[test]
+    fn irrational_eigenvalues() {
+        let m = matrix(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(
+            exact(&m),
+            vec!["5/2 - 1/2 * 33 ^ 1/2", "5/2 + 1/2 * 33 ^ 1/2"]
+        );
+    }
+
+    #[test]
+    fn quartic_eigenvalues() {
+        // (x^2 - 2x - 1)(x^2 + 2x - 4), no rational root
+        let m = matrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![-4, -6, 9, 0],
+        ]);
+        assert_eq!(
+            exact(&m),
+            vec!["-1 - 5 ^ 1/2", "1 - 2 ^ 1/2", "-1 + 5 ^ 1/2", "1 + 2 ^ 1/2"]
+        );
+        let expected = [
+            -1.0 - 5.0_f64.sqrt(),
+            1.0 - 2.0_f64.sqrt(),
+            5.0_f64.sqrt() - 1.0,
+            1.0 + 2.0_f64.sqrt(),
+        ];
+        // (x^2 - 2)(x^2 - 2x - 1), with a cubic term
+        let shifted = matrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![-2, -4, 3, 2],
+        ]);
+        let expected_shifted = [
+            -(2.0_f64.sqrt()),
+            1.0 - 2.0_f64.sqrt(),
+            2.0_f64.sqrt(),
+            1.0 + 2.0_f64.sqrt(),
+        ];
+        // x^4 - 4x^2 + 1, x^2 = 2 ± 3^1/2 isn't rational
+        let nested = matrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![-1, 0, 4, 0],
+        ]);
+        let r = (2.0 + 3.0_f64.sqrt()).sqrt();
+        let expected_nested = [-r, -1.0 / r, 1.0 / r, r];
+        for (m, expected) in [
+            (m, expected),
+            (shifted, expected_shifted),
+            (nested, expected_nested),
+        ] {
+            let Eigenvalues::Exact(values) = m.eigenvalues().unwrap() else {
+                panic!("expected exact eigenvalues");
+            };
+            assert_eq!(values.len(), 4);
+            for (v, e) in values.iter().zip(expected) {
+                assert!((v.evaluate(&HashMap::new()).unwrap() - e).abs() < 1e-9);
+            }
+        }
+        // x^4 + x + 1 only has complex roots
+        let m = matrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![-1, -1, 0, 0],
+        ]);
+        assert!(matches!(m.eigenvalues().unwrap(), Eigenvalues::Numeric(_)));
+    }
+
+    #[test]
+    fn symbolic_eigenvalues() {
+        let a = Expression::new_variable("a");
+        let m = Matrix::from_rows(vec![
+            vec![a.clone(), Expression::one()],
+            vec![Expression::zero(), a.clone()],
+        ])
+        .unwrap();
+        assert_eq!(exact(&m).len(), 2);
+        let spaces = m.eigenvectors().unwrap();
+        assert_eq!(spaces.len(), 1);
+        assert_eq!(spaces[0].multiplicity, 2);
+        assert!(is_zero_expr(&(spaces[0].value.clone() - a).num_aggregate()));
+        assert_eq!(spaces[0].basis.len(), 1);
+        assert!(spaces[0].basis[0].get(0, 0).is_one());
+        assert!(is_zero_expr(spaces[0].basis[0].get(1, 0)));
+    }
+
+    #[test]
+    fn numeric_fallback() {
+        let m = matrix(vec![vec![0, -1], vec![1, 0]]);
+        match m.eigenvalues().unwrap() {
+            Eigenvalues::Exact(_) => panic!("rotation has no real eigenvalues"),
+            Eigenvalues::Numeric(v) => {
+                assert_eq!(v.len(), 2);
+                assert!(v[0].re.abs() < 1e-9 && (v[0].im.abs() - 1.0).abs() < 1e-9);
+            }
+        }
+        let m = matrix(vec![vec![1, 1, 0], vec![1, 2, 1], vec![0, 1, 4]]);
+        assert!(matches!(m.eigenvalues().unwrap(), Eigenvalues::Numeric(_)));
+        let spaces = m.eigenvectors().unwrap();
+        assert_eq!(spaces.len(), 3);
+        for space in spaces {
+            let v = &space.basis[0];
+            let r = ((m.clone() * v.clone()).unwrap() - v.scale(&space.value)).unwrap();
+            for i in 0..3 {
+                assert!(r.get(i, 0).near(&Expression::zero(), 1e-6).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn block_triangular() {
+        let (a, b, c) = (
+            Expression::new_variable("a"),
+            Expression::new_variable("b"),
+            Expression::new_variable("c"),
+        );
+        let zero = Expression::zero;
+        let m = Matrix::from_rows(vec![
+            vec![a.clone(), zero(), zero()],
+            vec![zero(), b.clone(), zero()],
+            vec![zero(), zero(), c.clone()],
+        ])
+        .unwrap();
+        assert_eq!(exact(&m), vec!["a", "b", "c"]);
+        // a cubic characteristic polynomial, split into 1 x 1 and 2 x 2
+        let m = Matrix::from_rows(vec![
+            vec![a.clone(), b.clone(), c.clone()],
+            vec![zero(), b.clone(), Expression::one()],
+            vec![zero(), Expression::one(), b.clone()],
+        ])
+        .unwrap();
+        let values = exact(&m);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0], "a");
+        let m = matrix(vec![vec![5, 0, 0], vec![1, 1, 2], vec![7, 3, 4]]);
+        assert_eq!(
+            exact(&m),
+            vec!["5/2 - 1/2 * 33 ^ 1/2", "5", "5/2 + 1/2 * 33 ^ 1/2"]
+        );
+        let m = Matrix::from_rows(vec![
+            vec![a.clone(), b.clone(), c.clone()],
+            vec![b.clone(), c.clone(), a.clone()],
+            vec![c, a, b],
+        ])
+        .unwrap();
+        assert!(m.eigenvalues().is_err());
+    }
+
+    #[test]
+    fn symbolic_entries_numeric_polynomial() {
+        // companion matrix of x^3 - 2, an irreducible cubic
+        let n = |v: i64| Expression::from(v);
+        let root2 = n(2).pow(Expression::one() / n(2));
+        let m = Matrix::from_rows(vec![
+            vec![n(0), n(0), root2.clone() * root2],
+            vec![n(1), n(0), n(0)],
+            vec![n(0), n(1), n(0)],
+        ])
+        .unwrap();
+        match m.eigenvalues().unwrap() {
+            Eigenvalues::Exact(_) => panic!("x^3 - 2 has complex roots"),
+            Eigenvalues::Numeric(v) => {
+                assert_eq!(v.len(), 3);
+                let real: Vec<_> = v.iter().filter(|v| v.im == 0.0).collect();
+                assert_eq!(real.len(), 1);
+                assert!((real[0].re - 2.0_f64.cbrt()).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn large_entries() {
+        // the rational root search gives up and the values are numeric
+        let m = matrix(vec![vec![1000000007, 3], vec![5, 999999937]]);
+        let trace = 1000000007.0 + 999999937.0;
+        match m.eigenvalues().unwrap() {
+            Eigenvalues::Exact(_) => panic!("expected numeric eigenvalues"),
+            Eigenvalues::Numeric(v) => {
+                assert_eq!(v.len(), 2);
+                assert!(((v[0].re + v[1].re) - trace).abs() < 1e-3 * trace);
+            }
+        }
+        let m = matrix(vec![vec![1_000_000_000_000, 1], vec![1, 1_000_000_000_039]]);
+        assert!(matches!(m.eigenvalues().unwrap(), Eigenvalues::Numeric(_)));
+    }
+
+    #[test]
+    fn eigenvectors() {
+        let m = matrix(vec![vec![2, 1], vec![1, 2]]);
+        let spaces = m.eigenvectors().unwrap();
+        assert_eq!(spaces[0].value.to_string(), "1");
+        assert_eq!(spaces[0].basis[0].to_string(), "[[-1], [1]]");
+        assert_eq!(spaces[1].value.to_string(), "3");
+        assert_eq!(spaces[1].basis[0].to_string(), "[[1], [1]]");
+        let m = matrix(vec![vec![1, 2], vec![3, 4]]);
+        for space in m.eigenvectors().unwrap() {
+            let v = &space.basis[0];
+            let r = ((m.clone() * v.clone()).unwrap() - v.scale(&space.value)).unwrap();
+            assert!(is_zero_expr(&r.get(0, 0).clone().num_aggregate()));
+            assert!(is_zero_expr(&r.get(1, 0).clone().num_aggregate()));
+        }
+    }
+}
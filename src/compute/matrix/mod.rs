@@ -0,0 +1,267 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::{
+    ast::tree::Expression,
+    compute::{evaluate::is_zero_expr, num_aggregate::NumAggregate},
+};
+
+pub mod eigen;
+mod roots;
+
+/// Dense row-major matrix of expressions.
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Expression>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatrixError {
+    reason: &'static str,
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Matrix operation failed: {}", self.reason)
+    }
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+        Matrix {
+            rows,
+            cols,
+            data: vec![Expression::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Matrix {
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, Expression::one());
+        }
+        m
+    }
+
+    pub fn from_rows(rows: Vec<Vec<Expression>>) -> Result<Matrix, MatrixError> {
+        let n_rows = rows.len();
+        let n_cols = rows.first().map_or(0, |r| r.len());
+        let mut data = vec![];
+        for row in rows {
+            if row.len() != n_cols {
+                return Err(MatrixError {
+                    reason: "rows have different lengths",
+                });
+            }
+            data.extend(row);
+        }
+        Ok(Matrix {
+            rows: n_rows,
+            cols: n_cols,
+            data,
+        })
+    }
+
+    /// Column vector.
+    pub fn from_column(column: Vec<Expression>) -> Matrix {
+        Matrix {
+            rows: column.len(),
+            cols: 1,
+            data: column,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &Expression {
+        &self.data[i * self.cols + j]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, v: Expression) {
+        self.data[i * self.cols + j] = v;
+    }
+
+    pub fn row(&self, i: usize) -> &[Expression] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> Vec<Expression> {
+        (0..self.rows).map(|i| self.get(i, j).clone()).collect()
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut m = Matrix::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                m.set(j, i, self.get(i, j).clone());
+            }
+        }
+        m
+    }
+
+    pub fn trace(&self) -> Result<Expression, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError {
+                reason: "trace of a non-square matrix",
+            });
+        }
+        let mut t = Expression::zero();
+        for i in 0..self.rows {
+            t = (t + self.get(i, i).clone()).num_aggregate();
+        }
+        Ok(t)
+    }
+
+    pub fn scale(&self, k: &Expression) -> Matrix {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .map(|v| (k.clone() * v.clone()).num_aggregate())
+                .collect(),
+        }
+    }
+
+    /// True if every entry is a number.
+    pub fn is_numeric(&self) -> bool {
+        self.data.iter().all(|v| v.is_num())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.data.iter().all(is_zero_expr)
+    }
+}
+
+impl NumAggregate for Matrix {
+    fn num_aggregate(self) -> Self {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.into_iter().map(|v| v.num_aggregate()).collect(),
+        }
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<String> = (0..self.rows)
+            .map(|i| {
+                let row: Vec<String> = self.row(i).iter().map(|v| v.to_string()).collect();
+                format!("[{}]", row.join(", "))
+            })
+            .collect();
+        write!(f, "[{}]", rows.join(", "))
+    }
+}
+
+impl Neg for Matrix {
+    type Output = Matrix;
+
+    fn neg(self) -> Self::Output {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .into_iter()
+                .map(|v| (-v).num_aggregate())
+                .collect(),
+        }
+    }
+}
+
+impl Add for Matrix {
+    type Output = Result<Matrix, MatrixError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.rows != rhs.rows || self.cols != rhs.cols {
+            return Err(MatrixError {
+                reason: "shape mismatch",
+            });
+        }
+        Ok(Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .into_iter()
+                .zip(rhs.data)
+                .map(|(a, b)| (a + b).num_aggregate())
+                .collect(),
+        })
+    }
+}
+
+impl Sub for Matrix {
+    type Output = Result<Matrix, MatrixError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Result<Matrix, MatrixError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.cols != rhs.rows {
+            return Err(MatrixError {
+                reason: "shape mismatch",
+            });
+        }
+        let mut m = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for j in 0..rhs.cols {
+                let mut s = Expression::zero();
+                for k in 0..self.cols {
+                    s = (s + self.get(i, k).clone() * rhs.get(k, j).clone()).num_aggregate();
+                }
+                m.set(i, j, s);
+            }
+        }
+        Ok(m)
+    }
+}
+
+#[cfg(test)]
+mod matrix_tests {
+    use crate::ast::tree::Expression;
+
+    use super::Matrix;
+
+    #[test]
+    fn arithmetic() {
+        let a = Expression::new_variable("a");
+        let m = Matrix::from_rows(vec![
+            vec![Expression::from(1), a.clone()],
+            vec![Expression::from(0), Expression::from(2)],
+        ])
+        .unwrap();
+        let p = (m.clone() * Matrix::identity(2)).unwrap();
+        assert_eq!(p.to_string(), "[[1, a], [0, 2]]");
+        let s = (m.clone() + m.clone()).unwrap();
+        assert_eq!(s.to_string(), "[[2, a + a], [0, 4]]");
+        assert_eq!(m.trace().unwrap().to_string(), "3");
+        assert_eq!(m.transpose().to_string(), "[[1, 0], [a, 2]]");
+        assert!((m.clone() - m).unwrap().is_zero());
+        assert!(Matrix::from_rows(vec![vec![a.clone()], vec![a.clone(), a]]).is_err());
+    }
+}
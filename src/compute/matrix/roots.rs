@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::tree::Expression,
+    compute::{
+        evaluate::{is_zero_expr, Evaluate},
+        num_aggregate::NumAggregate,
+    },
+    math_op::pow::Pow,
+    smart_num::{
+        rational::{RationalNum, ToRational},
+        SmartNum, ToSmartNum,
+    },
+};
+
+fn half() -> Expression {
+    Expression::from(SmartNum::new_rational(1, 1, 2).unwrap())
+}
+
+/// Integer coefficients whose absolute values exceed this are not
+/// searched for rational roots, trial division would take too long.
+const MAX_DIVISOR_SEARCH: i128 = 1 << 40;
+/// Rational root candidates p / q tried per deflation at most.
+const MAX_CANDIDATES: usize = 1 << 16;
+
+/// nominator / denominator in lowest terms, None when it doesn't fit.
+fn rational(nominator: i128, denominator: i128) -> Option<Expression> {
+    let g = gcd(nominator, denominator).max(1);
+    let (nominator, denominator) = (nominator / g, denominator / g);
+    let sign = if (nominator < 0) != (denominator < 0) && nominator != 0 {
+        -1
+    } else {
+        1
+    };
+    let r = RationalNum::new(
+        sign,
+        u64::try_from(nominator.unsigned_abs()).ok()?,
+        u64::try_from(denominator.unsigned_abs()).ok()?,
+    )?;
+    Some(Expression::from(r))
+}
+
+/// **s** * sqrt(**r**) as an expression.
+fn surd(s: Expression, r: i128) -> Option<Expression> {
+    let r = Expression::from(i64::try_from(r).ok()?);
+    Some((s * r.pow(half())).num_aggregate())
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Positive divisors of **v**, None when |v| exceeds `MAX_DIVISOR_SEARCH`.
+fn divisors(v: i128) -> Option<Vec<i128>> {
+    let v = v.abs();
+    if v > MAX_DIVISOR_SEARCH {
+        return None;
+    }
+    let mut d = vec![];
+    let mut i = 1;
+    while i * i <= v {
+        if v % i == 0 {
+            d.push(i);
+            if i * i != v {
+                d.push(v / i);
+            }
+        }
+        i += 1;
+    }
+    Some(d)
+}
+
+/// Largest **s** with s * s dividing **v**, i.e. v = s^2 * r. None when
+/// **v** exceeds `MAX_DIVISOR_SEARCH`.
+fn square_part(v: i128) -> Option<(i128, i128)> {
+    if v.abs() > MAX_DIVISOR_SEARCH {
+        return None;
+    }
+    let mut s = 1;
+    let mut r = v;
+    let mut i = 2;
+    while i * i <= r {
+        while r % (i * i) == 0 {
+            r /= i * i;
+            s *= i;
+        }
+        i += 1;
+    }
+    Some((s, r))
+}
+
+/// Integer coefficients proportional to the rational ones, lowest degree first.
+fn to_integer_coefficients(coefficient: &[RationalNum]) -> Option<Vec<i128>> {
+    let mut l: i128 = 1;
+    for c in coefficient {
+        let d = c.denominator as i128;
+        l = (l / gcd(l, d)).checked_mul(d)?;
+    }
+    coefficient
+        .iter()
+        .map(|c| (c.sign as i128 * c.nominator as i128).checked_mul(l / c.denominator as i128))
+        .collect()
+}
+
+/// Σ a_k p^k q^(n-k), which vanishes iff p/q is a root. None on overflow.
+fn eval_homogeneous(a: &[i128], p: i128, q: i128) -> Option<i128> {
+    let n = a.len() - 1;
+    let mut s: i128 = 0;
+    for (k, a_k) in a.iter().enumerate() {
+        let term = a_k
+            .checked_mul(p.checked_pow(k as u32)?)?
+            .checked_mul(q.checked_pow((n - k) as u32)?)?;
+        s = s.checked_add(term)?;
+    }
+    Some(s)
+}
+
+/// Divide a(x) by (q x - p), which must be exact.
+fn deflate(a: &[i128], p: i128, q: i128) -> Option<Vec<i128>> {
+    let n = a.len() - 1;
+    let mut b = vec![0_i128; n];
+    b[n - 1] = a[n] / q;
+    for k in (1..n).rev() {
+        b[k - 1] = a[k].checked_add(p.checked_mul(b[k])?)? / q;
+    }
+    Some(b)
+}
+
+/// Real roots of a x^2 + b x + c with integer coefficients.
+fn integer_quadratic_roots(c: i128, b: i128, a: i128) -> Option<Vec<Expression>> {
+    let disc = b
+        .checked_mul(b)?
+        .checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if disc < 0 {
+        return None;
+    }
+    let (s, r) = square_part(disc)?;
+    if r == 1 {
+        let mut roots = vec![rational(-b - s, 2 * a)?, rational(-b + s, 2 * a)?];
+        if a < 0 {
+            roots.reverse();
+        }
+        return Some(roots);
+    }
+    let center = rational(-b, 2 * a)?;
+    let radius = surd(rational(s, 2 * a.abs())?, r)?;
+    Some(vec![
+        (center.clone() - radius.clone()).num_aggregate(),
+        (center + radius).num_aggregate(),
+    ])
+}
+
+/// Real roots of a_4 x^4 + a_2 x^2 + a_0 when x^2 is rational.
+fn biquadratic_roots(a: &[i128]) -> Option<Vec<Expression>> {
+    // solve for y = x^2 first
+    let disc = a[2]
+        .checked_mul(a[2])?
+        .checked_sub(a[4].checked_mul(a[0])?.checked_mul(4)?)?;
+    if disc < 0 {
+        return None;
+    }
+    let (s, r) = square_part(disc)?;
+    if r != 1 {
+        return None;
+    }
+    let mut roots = vec![];
+    for (p, q) in [(-a[2] - s, 2 * a[4]), (-a[2] + s, 2 * a[4])] {
+        let (p, q) = if q < 0 { (-p, -q) } else { (p, q) };
+        if p < 0 {
+            return None;
+        }
+        let radius = sqrt_of(rational(p, q)?);
+        roots.push((-radius.clone()).num_aggregate());
+        roots.push(radius);
+    }
+    Some(roots)
+}
+
+/// Square root of **v**, taken out of the radical as far as possible
+/// when **v** is a non-negative rational.
+fn sqrt_of(v: Expression) -> Expression {
+    let exact = v.clone().to_rational().and_then(|r| {
+        if r.nominator == 0 {
+            return Some(Expression::zero());
+        }
+        if r.sign < 0 {
+            return None;
+        }
+        // sqrt(p / q) = sqrt(p * q) / q
+        let (p, q) = (r.nominator as i128, r.denominator as i128);
+        let (s, k) = square_part(p.checked_mul(q)?)?;
+        if k == 1 {
+            rational(s, q)
+        } else {
+            surd(rational(s, q)?, k)
+        }
+    });
+    exact.unwrap_or_else(|| v.pow(half()).num_aggregate())
+}
+
+/// Real roots of y^2 + b y + c moved by -**shift**, None when they are
+/// complex.
+fn real_quadratic_roots(
+    b: Expression,
+    c: Expression,
+    shift: &Expression,
+) -> Option<Vec<Expression>> {
+    let disc = (b.clone() * b.clone() / Expression::from(4) - c).num_aggregate();
+    if !is_zero_expr(&disc) && disc.evaluate(&HashMap::new()).ok()? < 0.0 {
+        return None;
+    }
+    let radius = sqrt_of(disc);
+    let center = (-b * half() - shift.clone()).num_aggregate();
+    Some(vec![
+        (center.clone() - radius.clone()).num_aggregate(),
+        (center + radius).num_aggregate(),
+    ])
+}
+
+/// Real roots of the quartic a_4 x^4 + ... + a_0 by Ferrari's method,
+/// as long as its resolvent cubic has a rational root. None when a root
+/// is complex or the resolvent cubic has no rational root, the quartic
+/// then needs the roots of a cubic.
+fn ferrari_roots(a: &[i128]) -> Option<Vec<Expression>> {
+    let c = |k: usize| rational(a[k], a[4]);
+    let (b, c, d, e) = (c(3)?, c(2)?, c(1)?, c(0)?);
+    let n = |v: i64| Expression::from(v);
+    // x = y - b / 4 gives y^4 + p y^2 + q y + r
+    let shift = (b.clone() / n(4)).num_aggregate();
+    let p = (c.clone() - n(3) * b.clone().pow(2) / n(8)).num_aggregate();
+    let q = (b.clone().pow(3) / n(8) - b.clone() * c.clone() / n(2) + d.clone()).num_aggregate();
+    let r = (e - n(3) * b.clone().pow(4) / n(256) + b.clone().pow(2) * c / n(16) - b * d / n(4))
+        .num_aggregate();
+    let quadratics = if is_zero_expr(&q) {
+        // a quadratic in y^2
+        real_quadratic_roots(p, r, &Expression::zero())?
+            .into_iter()
+            .map(|z| (Expression::zero(), -z))
+            .collect()
+    } else {
+        // (y^2 + p / 2 + m)^2 = 2 m (y - q / 4m)^2 for a root m > 0 of the
+        // resolvent 8 m^3 + 8 p m^2 + (2 p^2 - 8 r) m - q^2
+        let resolvent = [
+            -q.clone().pow(2),
+            n(2) * p.clone().pow(2) - n(8) * r,
+            n(8) * p.clone(),
+            n(8),
+        ]
+        .into_iter()
+        .map(|v| v.num_aggregate().to_rational())
+        .collect::<Option<Vec<RationalNum>>>()?;
+        let mut res = to_integer_coefficients(&resolvent)?;
+        let m = loop {
+            let (mp, mq) = find_rational_root(&res)??;
+            if mp > 0 {
+                break rational(mp, mq)?;
+            }
+            res = deflate(&res, mp, mq)?;
+        };
+        let s = sqrt_of((n(2) * m.clone()).num_aggregate());
+        let t = (q / (n(4) * m.clone())).num_aggregate();
+        let base = (p / n(2) + m).num_aggregate();
+        // y^2 + p / 2 + m = ±s (y - t)
+        vec![
+            (-s.clone(), base.clone() + s.clone() * t.clone()),
+            (s.clone(), base - s * t),
+        ]
+    };
+    let mut roots = vec![];
+    for (b, c) in quadratics {
+        roots.extend(real_quadratic_roots(b, c.num_aggregate(), &shift)?);
+    }
+    Some(roots)
+}
+
+/// A rational root p / q of **a**, by the rational root theorem.
+fn find_rational_root(a: &[i128]) -> Option<Option<(i128, i128)>> {
+    let qs = divisors(a[a.len() - 1])?;
+    let ps = divisors(a[0])?;
+    if qs.len().saturating_mul(ps.len()) > MAX_CANDIDATES {
+        return None;
+    }
+    for &q in &qs {
+        for &p in &ps {
+            if gcd(p, q) != 1 {
+                continue;
+            }
+            for p in [-p, p] {
+                if eval_homogeneous(a, p, q)? == 0 {
+                    return Some(Some((p, q)));
+                }
+            }
+        }
+    }
+    Some(None)
+}
+
+fn rational_roots(coefficient: &[RationalNum]) -> Option<Vec<Expression>> {
+    let mut a = to_integer_coefficients(coefficient)?;
+    let mut found: Vec<(i128, i128)> = vec![];
+    while a.len() > 1 {
+        let Some((p, q)) = find_rational_root(&a)? else {
+            break;
+        };
+        a = deflate(&a, p, q)?;
+        found.push((p, q));
+    }
+    // |p|, |q| <= MAX_DIVISOR_SEARCH, so the cross products fit
+    found.sort_by(|x, y| (x.0 * y.1).cmp(&(y.0 * x.1)));
+    let mut roots = found
+        .into_iter()
+        .map(|(p, q)| rational(p, q))
+        .collect::<Option<Vec<Expression>>>()?;
+    match a.len() - 1 {
+        0 => {}
+        2 => roots.extend(integer_quadratic_roots(a[0], a[1], a[2])?),
+        // nested radicals are left to Ferrari's method
+        4 if a[1] == 0 && a[3] == 0 => {
+            roots.extend(biquadratic_roots(&a).or_else(|| ferrari_roots(&a))?)
+        }
+        4 => roots.extend(ferrari_roots(&a)?),
+        _ => return None,
+    }
+    Some(roots)
+}
+
+/// Exact roots of Σ coefficient[k] x^k, repeated by multiplicity.
+/// With rational coefficients the rational roots are taken out first,
+/// a remaining quadratic is solved directly and a remaining quartic by
+/// Ferrari's method when its resolvent cubic has a rational root.
+/// Symbolic coefficients are only solved up to quadratics.
+///
+/// There is no cubic formula: an irreducible cubic either has a single
+/// real root, whose complex partners can't be returned anyway, or three
+/// real ones, which Cardano's formula only reaches through complex
+/// radicals.
+/// Returns **None** when the roots cannot be written down exactly,
+/// which includes complex roots, or when the coefficients are too large
+/// for the rational root search.
+pub(crate) fn exact_roots(coefficient: &[Expression]) -> Option<Vec<Expression>> {
+    let mut c: Vec<Expression> = coefficient
+        .iter()
+        .map(|v| v.clone().num_aggregate())
+        .collect();
+    while c.len() > 1 && is_zero_expr(&c[c.len() - 1]) {
+        c.pop();
+    }
+    let mut roots = vec![];
+    while c.len() > 1 && is_zero_expr(&c[0]) {
+        c.remove(0);
+        roots.push(Expression::zero());
+    }
+    let rational: Option<Vec<RationalNum>> = c.iter().map(|v| v.to_rational()).collect();
+    if let Some(r) = rational {
+        roots.extend(rational_roots(&r)?);
+        return Some(roots);
+    }
+    // floating point coefficients, e.g. from an overflowed product, are
+    // left to numeric_roots
+    if c.iter().all(|v| v.to_smart_num().is_ok()) {
+        return None;
+    }
+    match c.len() - 1 {
+        0 => {}
+        1 => roots.push((-c[0].clone() / c[1].clone()).num_aggregate()),
+        2 => {
+            let (a, b) = (c[2].clone(), c[1].clone());
+            let disc = (b.clone() * b.clone() - Expression::from(4) * a.clone() * c[0].clone())
+                .num_aggregate();
+            if let Ok(d) = (&disc).to_smart_num() {
+                if d.to_f64() < 0.0 {
+                    return None;
+                }
+            }
+            let radius = disc.pow(half());
+            let denominator = Expression::from(2) * a;
+            roots.push(((-b.clone() - radius.clone()) / denominator.clone()).num_aggregate());
+            roots.push(((-b + radius) / denominator).num_aggregate());
+        }
+        _ => return None,
+    }
+    Some(roots)
+}
+
+/// All complex roots of Σ coefficient[k] x^k by Durand-Kerner iteration,
+/// as (re, im) pairs.
+pub(crate) fn numeric_roots(coefficient: &[f64]) -> Vec<(f64, f64)> {
+    let mut c = coefficient.to_vec();
+    while c.len() > 1 && c[c.len() - 1] == 0.0 {
+        c.pop();
+    }
+    let n = c.len() - 1;
+    if n == 0 {
+        return vec![];
+    }
+    let lead = c[n];
+    let monic: Vec<f64> = c.iter().map(|v| v / lead).collect();
+    let eval = |z: (f64, f64)| {
+        let mut s = (0.0, 0.0);
+        for a in monic.iter().rev() {
+            s = (s.0 * z.0 - s.1 * z.1 + a, s.0 * z.1 + s.1 * z.0);
+        }
+        s
+    };
+    let mut z: Vec<(f64, f64)> = vec![];
+    let mut w = (1.0, 0.0);
+    for _ in 0..n {
+        z.push(w);
+        w = (w.0 * 0.4 - w.1 * 0.9, w.0 * 0.9 + w.1 * 0.4);
+    }
+    for _ in 0..1000 {
+        let mut delta = 0.0_f64;
+        for i in 0..n {
+            let mut d = (1.0, 0.0);
+            for j in 0..n {
+                if i != j {
+                    let t = (z[i].0 - z[j].0, z[i].1 - z[j].1);
+                    d = (d.0 * t.0 - d.1 * t.1, d.0 * t.1 + d.1 * t.0);
+                }
+            }
+            let p = eval(z[i]);
+            let norm = d.0 * d.0 + d.1 * d.1;
+            if norm == 0.0 {
+                continue;
+            }
+            let q = (
+                (p.0 * d.0 + p.1 * d.1) / norm,
+                (p.1 * d.0 - p.0 * d.1) / norm,
+            );
+            z[i] = (z[i].0 - q.0, z[i].1 - q.1);
+            delta = delta.max(q.0.abs() + q.1.abs());
+        }
+        if delta < 1e-15 {
+            break;
+        }
+    }
+    for v in z.iter_mut() {
+        if v.1.abs() < 1e-9 * (1.0 + v.0.abs()) {
+            v.1 = 0.0;
+        }
+    }
+    z.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    z
+}
@@ -2,4 +2,8 @@ pub mod derivative;
 pub mod num_aggregate;
 pub mod taylor_expansion;
 pub mod substitute;
-pub mod limit;
\ No newline at end of file
+pub mod limit;
+pub mod evaluate;
+pub mod matrix;
+pub mod doit;
+pub mod approximation;
\ No newline at end of file
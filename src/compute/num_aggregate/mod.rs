@@ -6,9 +6,19 @@ use crate::ast::{
 };
 
 use self::rules::{
-    add::add_eval_rule, cos::cos_eval_rule, div::div_eval_rule, exp::exp_eval_rule,
-    ln::ln_eval_rule, mul::mul_eval_rule, neg::neg_eval_rule, pow::pow_eval_rule,
-    sin::sin_eval_rule, sub::sub_eval_rule,
+    add::add_eval_rule,
+    cos::cos_eval_rule,
+    discrete::{factorial_eval_rule, floor_eval_rule},
+    div::div_eval_rule,
+    exp::exp_eval_rule,
+    function::{function_derivative_eval_rule, function_eval_rule},
+    ln::ln_eval_rule,
+    mul::mul_eval_rule,
+    neg::neg_eval_rule,
+    pow::pow_eval_rule,
+    sin::sin_eval_rule,
+    sub::sub_eval_rule,
+    unevaluated::unevaluated_eval_rule,
 };
 
 pub trait NumAggregate {
@@ -23,7 +33,7 @@ impl NumAggregate for Expression {
     fn num_aggregate(self) -> Self {
         match &self.root {
             AstNode::Operand(_) => self,
-            AstNode::Operator(operator) => match operator.descriptor {
+            AstNode::Operator(operator) => match &operator.descriptor {
                 OperatorType::Neg => neg_eval_rule(self.child),
                 OperatorType::Add => add_eval_rule(self.child),
                 OperatorType::Sub => sub_eval_rule(self.child),
@@ -34,6 +44,16 @@ impl NumAggregate for Expression {
                 OperatorType::Exp => exp_eval_rule(self.child),
                 OperatorType::Ln => ln_eval_rule(self.child),
                 OperatorType::Pow => pow_eval_rule(self.child),
+                OperatorType::Factorial => factorial_eval_rule(self.child),
+                OperatorType::Floor => floor_eval_rule(self.child),
+                OperatorType::Function(name) => function_eval_rule(name, self.child),
+                OperatorType::FunctionDerivative(name, wrt) => {
+                    function_derivative_eval_rule(name, wrt.clone(), self.child)
+                }
+                OperatorType::Derivative
+                | OperatorType::Integral
+                | OperatorType::Limit
+                | OperatorType::Sum => unevaluated_eval_rule(operator, self.child),
             },
         }
     }
@@ -43,6 +63,7 @@ impl NumAggregate for Expression {
 mod eval_tests {
     use crate::{
         ast::tree::Expression,
+        math_op::pow::Pow,
         smart_num::{val_holder::IsClose, SmartNum, ToSmartNum},
     };
 
@@ -60,4 +81,43 @@ mod eval_tests {
         let expected = SmartNum::new_rational(1, 3, 5).unwrap();
         assert!(ans.is_close(expected, 1e-9));
     }
+
+    #[test]
+    fn powers_of_zero_and_one() {
+        let x = Expression::new_variable("x");
+        let power = |b: i64, e: Expression| Expression::from(b).pow(e).num_aggregate();
+        assert_eq!(power(0, Expression::from(3)).to_string(), "0");
+        assert_eq!(power(0, Expression::zero()).to_string(), "1");
+        assert_eq!(power(1, Expression::from(5)).to_string(), "1");
+        assert_eq!(power(1, x.clone()).to_string(), "1");
+        // the sign of x is unknown, 0^x stays
+        assert_eq!(power(0, x.clone()), Expression::zero().pow(x));
+    }
+
+    #[test]
+    fn large_exponents() {
+        let power = |b: Expression, k: i64| b.pow(Expression::from(k)).num_aggregate();
+        let half = Expression::one() / Expression::from(2);
+        assert_eq!(power(Expression::from(2), 10).to_string(), "1024");
+        assert_eq!(
+            power(Expression::from(2) / Expression::from(3), -2).to_string(),
+            "9/4"
+        );
+        assert_eq!(
+            power(-Expression::one(), 1_000_000_000_001).to_string(),
+            "-1"
+        );
+        assert_eq!(
+            power(-Expression::one(), 1_000_000_000_000).to_string(),
+            "1"
+        );
+        // far too large to fold, left as it is
+        let p = power(half.clone(), 1_000_000_000_000);
+        assert_eq!(
+            p,
+            half.num_aggregate()
+                .pow(Expression::from(1_000_000_000_000_i64))
+        );
+        assert!(!power(Expression::from(2), 100).is_num());
+    }
 }
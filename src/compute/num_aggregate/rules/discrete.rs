@@ -0,0 +1,59 @@
+use crate::ast::op::operand::AstOperand;
+use crate::ast::tree::{AstNode, Expression};
+use crate::compute::num_aggregate::NumAggregate;
+use crate::math_op::discrete::{factorial, floor};
+use crate::smart_num::rational::ToRational;
+
+pub(crate) fn factorial_eval_rule(mut child: Vec<Expression>) -> Expression {
+    let sub = child.pop().unwrap().num_aggregate();
+    match &sub.root {
+        // 20! is the largest one that fits in i64
+        AstNode::Operand(AstOperand::Num(v)) => match v.to_i64() {
+            Some(n) if (0..=20).contains(&n) => Expression::from((1..=n).product::<i64>()),
+            _ => factorial(sub),
+        },
+        _ => factorial(sub),
+    }
+}
+
+pub(crate) fn floor_eval_rule(mut child: Vec<Expression>) -> Expression {
+    let sub = child.pop().unwrap().num_aggregate();
+    match &sub.root {
+        AstNode::Operand(AstOperand::Num(v)) if v.is_exact() => match v.to_rational() {
+            Some(r) => {
+                let (n, d) = (r.nominator as i64, r.denominator as i64);
+                if r.sign < 0 {
+                    Expression::from(-((n + d - 1) / d))
+                } else {
+                    Expression::from(n / d)
+                }
+            }
+            None => floor(sub),
+        },
+        _ => floor(sub),
+    }
+}
+
+#[cfg(test)]
+mod discrete_eval_tests {
+    use crate::{
+        ast::tree::Expression,
+        compute::num_aggregate::NumAggregate,
+        math_op::discrete::{factorial, floor},
+    };
+
+    #[test]
+    fn fold_numbers() {
+        let five = Expression::from(5);
+        assert_eq!(factorial(five.clone()).num_aggregate().to_string(), "120");
+        assert_eq!(
+            factorial(Expression::zero()).num_aggregate().to_string(),
+            "1"
+        );
+        let half = Expression::from(7) / Expression::from(2);
+        assert_eq!(floor(half.clone()).num_aggregate().to_string(), "3");
+        assert_eq!(floor(-half).num_aggregate().to_string(), "-4");
+        let n = Expression::new_variable("n");
+        assert_eq!(factorial(n).num_aggregate().to_string(), "factorial(n)");
+    }
+}
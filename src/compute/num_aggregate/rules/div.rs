@@ -9,6 +9,8 @@ pub(crate) fn div_eval_rule(mut child: Vec<Expression>) -> Expression {
         Expression::from(l.to_smart_num().unwrap() / r.to_smart_num().unwrap())
     } else if l.is_zero() {
         Expression::from(0_i64)
+    } else if r.is_one() {
+        l
     } else {
         l / r
     }
@@ -0,0 +1,19 @@
+use crate::ast::tree::Expression;
+use crate::compute::num_aggregate::NumAggregate;
+use crate::math_op::function::{function, function_derivative};
+
+pub(crate) fn function_eval_rule(name: &str, child: Vec<Expression>) -> Expression {
+    function(name, child.into_iter().map(|c| c.num_aggregate()).collect())
+}
+
+pub(crate) fn function_derivative_eval_rule(
+    name: &str,
+    wrt: Vec<usize>,
+    child: Vec<Expression>,
+) -> Expression {
+    function_derivative(
+        name,
+        wrt,
+        child.into_iter().map(|c| c.num_aggregate()).collect(),
+    )
+}
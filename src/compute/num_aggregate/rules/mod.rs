@@ -7,4 +7,7 @@ pub(crate) mod sin;
 pub(crate) mod cos;
 pub(crate) mod exp;
 pub(crate) mod ln;
-pub(crate) mod pow;
\ No newline at end of file
+pub(crate) mod pow;
+pub(crate) mod function;
+pub(crate) mod discrete;
+pub(crate) mod unevaluated;
\ No newline at end of file
@@ -1,28 +1,80 @@
 use crate::ast::op::operand::AstOperand;
-use crate::ast::op::operator::OperatorType;
 use crate::ast::tree::{AstNode, Expression};
 use crate::compute::num_aggregate::NumAggregate;
 use crate::math_op::pow::Pow;
+use crate::smart_num::{
+    rational::{RationalNum, ToRational},
+    SmartNum, ToSmartNum,
+};
+
+/// Largest magnitude of an exactly folded power, keeping i64 arithmetic safe.
+const MAX_EXACT_POWER: u64 = 1_000_000_000_000_000;
+
+/// Largest exponent folded exactly, 2^k already passes the bound above
+/// for smaller k, so only 0 and ±1 have larger exact powers.
+const MAX_EXACT_EXPONENT: u64 = 64;
+
+fn exact_power(base: &SmartNum, exponent: &Expression) -> Option<Expression> {
+    let k = exponent.to_smart_num().ok()?.to_i64()?;
+    if !base.is_exact() {
+        return None;
+    }
+    let b = base.to_rational()?;
+    if b.nominator == 0 {
+        return match k.signum() {
+            1 => Some(Expression::zero()),
+            0 => Some(Expression::one()),
+            _ => None,
+        };
+    }
+    if b.nominator == b.denominator {
+        let odd = k % 2 != 0;
+        return Some(Expression::from(if b.sign < 0 && odd { -1_i64 } else { 1 }));
+    }
+    if k.unsigned_abs() > MAX_EXACT_EXPONENT {
+        return None;
+    }
+    // exponentiation by squaring, giving up on overflow
+    let (mut result, mut square) = (RationalNum::from(1_i64), b);
+    let mut e = k.unsigned_abs();
+    while e > 0 {
+        if e & 1 == 1 {
+            result = result.checked_mul(square)?;
+        }
+        e >>= 1;
+        if e > 0 {
+            square = square.checked_mul(square)?;
+        }
+    }
+    if result.nominator > MAX_EXACT_POWER || result.denominator > MAX_EXACT_POWER {
+        return None;
+    }
+    if k < 0 {
+        result = RationalNum::from(1_i64).checked_div(result)?;
+    }
+    Some(Expression::from(result))
+}
 
 pub(crate) fn pow_eval_rule(mut child: Vec<Expression>) -> Expression {
     let r = child.pop().unwrap().num_aggregate();
-    let mut sub = child.pop().unwrap().num_aggregate();
+    let sub = child.pop().unwrap().num_aggregate();
     match &sub.root {
         AstNode::Operand(operand) => match operand {
             AstOperand::Variable(_) => sub.pow(r),
             AstOperand::Num(v) => {
-                if v.is_zero() {
+                if v.is_zero() && r.is_zero() {
                     Expression::from(1_i64)
+                } else if v.is_zero() && r.is_num() && (&r).to_smart_num().unwrap().sign() > 0 {
+                    Expression::from(0_i64)
                 } else if v.is_one() {
-                    r
+                    Expression::from(1_i64)
+                } else if let Some(n) = exact_power(v, &r) {
+                    n
                 } else {
                     sub.pow(r)
                 }
             }
         },
-        AstNode::Operator(operator) => match operator.descriptor {
-            OperatorType::Ln => sub.child.pop().unwrap(),
-            _ => sub.pow(r),
-        },
+        AstNode::Operator(_) => sub.pow(r),
     }
 }
@@ -0,0 +1,12 @@
+use crate::ast::{
+    op::operator::AstOperator,
+    tree::{AstNode, Expression},
+};
+use crate::compute::num_aggregate::NumAggregate;
+
+pub(crate) fn unevaluated_eval_rule(operator: &AstOperator, child: Vec<Expression>) -> Expression {
+    Expression {
+        root: AstNode::Operator(operator.clone()),
+        child: child.into_iter().map(|c| c.num_aggregate()).collect(),
+    }
+}
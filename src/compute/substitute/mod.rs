@@ -1,10 +1,15 @@
 use std::fmt::Display;
 
 use crate::ast::{
-    op::operand::{AstOperand, Variable},
+    op::{
+        operand::{AstOperand, Variable},
+        operator::OperatorType,
+    },
     tree::{AstNode, Expression},
 };
 
+use self::rules::unevaluated::{bound_substitute_rule, derivative_substitute_rule};
+
 mod rules;
 
 pub(crate) trait Substitute<T, U> {
@@ -38,6 +43,12 @@ impl<'a> Substitute<&'a Variable, &'a Expression> for Expression {
                     }
                 }
             },
+            AstNode::Operator(op) if op.descriptor.binds_variable() => {
+                bound_substitute_rule(self, src, v)?
+            }
+            AstNode::Operator(op) if op.descriptor == OperatorType::Derivative => {
+                derivative_substitute_rule(self, src, v)?
+            }
             AstNode::Operator(_) => {
                 let mut s = self;
                 let mut c: Vec<Expression> = vec![];
@@ -72,7 +83,11 @@ impl Substitute<&Expression, &Expression> for Expression {
 
 #[cfg(test)]
 mod substitute_tests {
-    use crate::{ast::tree::Expression, compute::num_aggregate::NumAggregate};
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::num_aggregate::NumAggregate,
+        math_op::unevaluated::{diff, sum},
+    };
 
     use super::Substitute;
 
@@ -86,4 +101,37 @@ mod substitute_tests {
         println!("{}", y_s);
         assert_eq!(y_s.to_string(), "1 + b * b");
     }
+
+    #[test]
+    fn bound_variables() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let n = Variable::new_variable("n");
+        let vx = Variable::new_variable("x");
+        let s = sum(
+            x.clone() * Expression::from(n.clone()),
+            &n,
+            Expression::one(),
+            y.clone(),
+        );
+        // the dummy variable is left alone
+        let r = s.clone().substitute(&n, &x).unwrap();
+        assert_eq!(r, s);
+        // and renamed before it could capture the substituted expression
+        let r = s
+            .clone()
+            .substitute(&vx, &Expression::from(n.clone()))
+            .unwrap();
+        assert_eq!(r.to_string(), "Sum(n * n_1, n_1, 1, y)");
+        let r = s.substitute(&Variable::new_variable("y"), &x).unwrap();
+        assert_eq!(r.to_string(), "Sum(x * n, n, 1, x)");
+        // a derivative is evaluated before its variable is replaced
+        let d = diff(x.clone() * x.clone() * y.clone(), &vx);
+        let r = d.clone().substitute(&vx, &Expression::from(2)).unwrap();
+        assert_eq!(r.num_aggregate().to_string(), "4 * y");
+        let r = d
+            .substitute(&Variable::new_variable("y"), &Expression::from(3))
+            .unwrap();
+        assert_eq!(r.to_string(), "Derivative(x * x * 3, x)");
+    }
 }
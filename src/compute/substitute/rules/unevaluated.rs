@@ -0,0 +1,69 @@
+use crate::{
+    ast::{
+        op::{operand::Variable, operator::OperatorType},
+        tree::{AstNode, Expression},
+    },
+    compute::{doit::DoIt, substitute::Substitute},
+};
+
+use super::super::SubstituteError;
+
+/// A variable named after **of** that is free in none of **exprs**.
+fn fresh_variable(of: &Variable, exprs: &[&Expression]) -> Variable {
+    (1_u64..)
+        .map(|k| Variable::new_variable(&format!("{}_{}", of.name, k)))
+        .find(|v| exprs.iter().all(|e| !e.contains_variable(v)))
+        .unwrap()
+}
+
+/// Substitute **u** by **v** in a node binding its second child in the
+/// first one. The dummy variable is never replaced, and it is renamed
+/// first whenever **v** mentions it, so that **v** is not captured.
+pub(crate) fn bound_substitute_rule(
+    expr: Expression,
+    u: &Variable,
+    v: &Expression,
+) -> Result<Expression, SubstituteError> {
+    let mut s = expr;
+    let bound = s.bound_variable().cloned().ok_or(SubstituteError {})?;
+    let mut child = s.child.into_iter();
+    let mut body = child.next().ok_or(SubstituteError {})?;
+    let mut dummy = child.next().ok_or(SubstituteError {})?;
+    if bound != *u {
+        if v.contains_variable(&bound) {
+            let fresh = fresh_variable(&bound, &[&body, v, &Expression::from(u.clone())]);
+            dummy = Expression::from(fresh.clone());
+            body = body.substitute(&bound, &dummy)?;
+        }
+        body = body.substitute(u, v)?;
+    }
+    let mut c = vec![body, dummy];
+    for expr in child {
+        c.push(expr.substitute(u, v)?);
+    }
+    s.child = c;
+    Ok(s)
+}
+
+/// d/dx f is a function of x, substituting x (or anything mentioning x)
+/// needs the derivative to be evaluated first.
+pub(crate) fn derivative_substitute_rule(
+    expr: Expression,
+    u: &Variable,
+    v: &Expression,
+) -> Result<Expression, SubstituteError> {
+    let of = expr.bound_variable().cloned().ok_or(SubstituteError {})?;
+    if of == *u || v.contains_variable(&of) {
+        let value = expr.clone().doit();
+        if matches!(&value.root, AstNode::Operator(op) if op.descriptor == OperatorType::Derivative)
+        {
+            return Err(SubstituteError {});
+        }
+        return value.substitute(u, v);
+    }
+    let mut s = expr;
+    let dummy = s.child.pop().ok_or(SubstituteError {})?;
+    let body = s.child.pop().ok_or(SubstituteError {})?;
+    s.child = vec![body.substitute(u, v)?, dummy];
+    Ok(s)
+}
@@ -0,0 +1,466 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::VecDeque,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    ast::tree::Expression,
+    compute::{evaluate::is_zero_expr, num_aggregate::NumAggregate},
+};
+
+use super::partial_expansion::{PartialExpansion, TaylorExpansionError};
+
+/*
+ * Truncated power series arithmetic. Every result is only known up to
+ * the lowest order its operands pin down, e.g. (x + O(x^3)) * (x + O(x^2))
+ * is x^2 + O(x^3): the O(x^2) of the right one only ever meets x.
+ * Operands of Laurent or Puiseux series are first brought to a common
+ * ramification, the exponents then line up index by index.
+*/
+
+type SeriesResult = Result<PartialExpansion, TaylorExpansionError<Expression>>;
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl PartialExpansion {
+    /// The series of a constant **c** around **at** in **of**.
+    pub fn constant(c: Expression, of: Expression, at: Expression, order: u64) -> PartialExpansion {
+        let mut coefficient = VecDeque::from(vec![Expression::zero(); order as usize + 1]);
+        coefficient[0] = c;
+        PartialExpansion {
+            order,
+            of,
+            at,
+            coefficient,
+            start: 0,
+            ramification: 1,
+            residual: None,
+            source: None,
+        }
+    }
+
+    /// The constant **c** with the same ramification as self.
+    fn constant_like(&self, c: Expression, order: u64) -> PartialExpansion {
+        let mut constant = PartialExpansion::constant(c, self.of.clone(), self.at.clone(), order);
+        constant.ramification = self.ramification;
+        constant
+    }
+
+    /// k-th coefficient, 0 beyond the order.
+    fn get(&self, k: u64) -> Expression {
+        self.coefficient
+            .get(k as usize)
+            .cloned()
+            .unwrap_or(Expression::zero())
+    }
+
+    /// Coefficient of (of - at)^(**e** / ramification), 0 below the start
+    /// and beyond the order.
+    pub(crate) fn get_exponent(&self, e: i64) -> Expression {
+        if e < self.start {
+            Expression::zero()
+        } else {
+            self.get((e - self.start) as u64)
+        }
+    }
+
+    /// Exponent of the last known coefficient, in units of 1 / ramification.
+    pub(crate) fn end(&self) -> i64 {
+        self.start + self.order as i64
+    }
+
+    /// Index of the first coefficient that doesn't vanish, order + 1 if
+    /// none up to the order does.
+    pub fn valuation(&self) -> u64 {
+        (0..=self.order)
+            .find(|k| !is_zero_expr(&self.get(*k)))
+            .unwrap_or(self.order + 1)
+    }
+
+    fn with_coefficients(
+        &self,
+        start: i64,
+        order: u64,
+        coefficient: Vec<Expression>,
+    ) -> PartialExpansion {
+        PartialExpansion {
+            order,
+            of: self.of.clone(),
+            at: self.at.clone(),
+            coefficient: coefficient.into_iter().map(|c| c.num_aggregate()).collect(),
+            start,
+            ramification: self.ramification,
+            residual: None,
+            source: None,
+        }
+    }
+
+    fn check_same_point(&self, other: &PartialExpansion) -> Result<(), TaylorExpansionError> {
+        if self.of == other.of && is_zero_expr(&(self.at.clone() - other.at.clone())) {
+            Ok(())
+        } else {
+            Err(TaylorExpansionError {
+                err_expr: other.of.clone(),
+                reason: "expansions at different points",
+            })
+        }
+    }
+
+    /// The same series with exponents in units of 1 / **ramification**,
+    /// which must be a multiple of the current one.
+    pub(crate) fn ramify(&self, ramification: u64) -> PartialExpansion {
+        let f = ramification / self.ramification;
+        if f <= 1 {
+            return self.clone();
+        }
+        let order = self.order * f;
+        let coefficient = (0..=order)
+            .map(|k| {
+                if k % f == 0 {
+                    self.get(k / f)
+                } else {
+                    Expression::zero()
+                }
+            })
+            .collect();
+        PartialExpansion {
+            ramification,
+            ..self.with_coefficients(self.start * f as i64, order, coefficient)
+        }
+    }
+
+    /// Both series at the same point with the same ramification.
+    fn aligned(self, other: PartialExpansion) -> Result<(Self, Self), TaylorExpansionError> {
+        self.check_same_point(&other)?;
+        let r = self.ramification / gcd(self.ramification, other.ramification) * other.ramification;
+        Ok((self.ramify(r), other.ramify(r)))
+    }
+
+    /// The same series starting at its first non-zero coefficient.
+    pub fn trim(&self) -> PartialExpansion {
+        let v = self.valuation();
+        if v == 0 || v > self.order {
+            return self.clone();
+        }
+        let coefficient = (v..=self.order).map(|k| self.get(k)).collect();
+        self.with_coefficients(self.start + v as i64, self.order - v, coefficient)
+    }
+
+    /// The same series with the smallest ramification its non-zero
+    /// exponents allow, e.g. back to a Laurent series once the fractional
+    /// exponents cancelled out. The remainder may only get coarser.
+    pub(crate) fn normalize(&self) -> PartialExpansion {
+        let trimmed = self.trim();
+        let g = (0..=trimmed.order)
+            .filter(|k| !is_zero_expr(&trimmed.get(*k)))
+            .fold(trimmed.ramification, |g, k| {
+                gcd(g, (trimmed.start + k as i64).unsigned_abs())
+            });
+        if g <= 1 || is_zero_expr(&trimmed.get(0)) {
+            return trimmed;
+        }
+        let (start, end) = (
+            trimmed.start / g as i64,
+            (trimmed.end() + 1).div_euclid(g as i64) - 1,
+        );
+        if end < start {
+            return trimmed;
+        }
+        let coefficient = (start..=end)
+            .map(|e| trimmed.get_exponent(e * g as i64))
+            .collect();
+        PartialExpansion {
+            ramification: trimmed.ramification / g,
+            ..trimmed.with_coefficients(start, (end - start) as u64, coefficient)
+        }
+    }
+
+    /// Series of 1 / self, there must be a known non-zero coefficient.
+    pub fn recip(&self) -> SeriesResult {
+        let v = self.valuation();
+        if v > self.order {
+            return Err(TaylorExpansionError {
+                err_expr: Expression::zero(),
+                reason: "division by a series without a known non-zero term",
+            });
+        }
+        // self = (of - at)^((start + v) / r) * b with b(at) = b_0 != 0
+        let b = self.trim();
+        let b0 = b.get(0);
+        // c_k = -(b_1 c_(k-1) + ... + b_k c_0) / b_0
+        let mut c: Vec<Expression> = vec![(Expression::one() / b0.clone()).num_aggregate()];
+        for k in 1..=b.order {
+            let mut s = Expression::zero();
+            for j in 1..=k {
+                s = s + b.get(j) * c[(k - j) as usize].clone();
+            }
+            c.push((-s / b0.clone()).num_aggregate());
+        }
+        Ok(b.with_coefficients(-b.start, b.order, c))
+    }
+
+    /// self ^ **k** for an integer **k**, by repeated squaring.
+    pub fn powi(&self, k: i64) -> SeriesResult {
+        let base = if k < 0 { self.recip()? } else { self.clone() };
+        let mut result: Option<PartialExpansion> = None;
+        let mut square = base;
+        let mut e = k.unsigned_abs();
+        while e > 0 {
+            if e & 1 == 1 {
+                result = Some(match result {
+                    None => square.clone(),
+                    Some(r) => (r * square.clone())?,
+                });
+            }
+            e >>= 1;
+            if e > 0 {
+                square = (square.clone() * square)?;
+            }
+        }
+        Ok(result.unwrap_or(self.constant_like(Expression::one(), self.order)))
+    }
+
+    /// Series of self(inner), where **inner** is expanded in another
+    /// variable and tends to the point self is expanded at. Self must be
+    /// a Taylor series and inner may not have negative exponents.
+    pub fn compose(&self, inner: &PartialExpansion) -> SeriesResult {
+        if self.start != 0 || self.ramification != 1 {
+            return Err(TaylorExpansionError {
+                err_expr: self.of.clone(),
+                reason: "an outer series that isn't a Taylor series",
+            });
+        }
+        let inner = inner.trim();
+        if inner.start < 0 && inner.valuation() <= inner.order {
+            return Err(TaylorExpansionError {
+                err_expr: inner.of.clone(),
+                reason: "an essential singularity",
+            });
+        }
+        let h0 = (inner.get_exponent(0) - self.at.clone()).num_aggregate();
+        if !is_zero_expr(&h0) {
+            return Err(TaylorExpansionError {
+                err_expr: inner.get_exponent(0),
+                reason: "inner series doesn't tend to the outer expansion point",
+            });
+        }
+        // h = inner - at from exponent 0 on starts at (x - x0)^v, so h^k
+        // is O((x - x0)^(k v))
+        let end = inner.end().max(0) as u64;
+        let coefficient = (0..=end as i64)
+            .map(|e| {
+                if e == 0 {
+                    Expression::zero()
+                } else {
+                    inner.get_exponent(e)
+                }
+            })
+            .collect();
+        let h = inner.with_coefficients(0, end, coefficient);
+        let v = h.valuation();
+        let order = if v > h.order {
+            h.order
+        } else {
+            h.order.min((self.order + 1) * v - 1)
+        };
+        let mut result = h.constant_like(self.get(0), order);
+        let mut h_k = h.constant_like(Expression::one(), order);
+        for k in 1..=self.order {
+            if k * v > order {
+                break;
+            }
+            h_k = (h_k * h.clone())?;
+            let term = h_k.scale(self.get(k));
+            result = (result + term)?;
+        }
+        Ok(result.truncate(order))
+    }
+
+    /// Every coefficient multiplied by **c**.
+    pub fn scale(&self, c: Expression) -> PartialExpansion {
+        let coefficient = self
+            .coefficient
+            .iter()
+            .map(|a| a.clone() * c.clone())
+            .collect();
+        self.with_coefficients(self.start, self.order, coefficient)
+    }
+
+    /// Term-wise derivative in **of**, known one power of (of - at) less
+    /// far. The constant term of a series starting at 0 is dropped.
+    pub fn derivative(&self) -> SeriesResult {
+        let r = self.ramification as i64;
+        let skip = if self.start == 0 { 1 } else { 0 };
+        if skip > self.order {
+            return Err(TaylorExpansionError {
+                err_expr: self.of.clone(),
+                reason: "no term known of the derivative",
+            });
+        }
+        let coefficient = (skip..=self.order)
+            .map(|k| {
+                let e = self.start + k as i64;
+                self.get(k) * Expression::from(e) / Expression::from(r)
+            })
+            .collect();
+        Ok(self.with_coefficients(self.start + skip as i64 - r, self.order - skip, coefficient))
+    }
+
+    /// Term-wise antiderivative in **of** vanishing at **at**, known one
+    /// power of (of - at) further. Fails on a (of - at)^-1 term, whose
+    /// integral is a logarithm.
+    pub fn integral(&self) -> SeriesResult {
+        let r = self.ramification as i64;
+        if (self.start..=self.end() + 1).contains(&-r)
+            && (-r > self.end() || !is_zero_expr(&self.get_exponent(-r)))
+        {
+            return Err(TaylorExpansionError {
+                err_expr: self.of.clone(),
+                reason: "a logarithmic term in the integral",
+            });
+        }
+        let mut coefficient: Vec<Expression> = (0..=self.order)
+            .map(|k| {
+                let e = self.start + k as i64 + r;
+                self.get(k) * Expression::from(r) / Expression::from(e)
+            })
+            .collect();
+        // a series starting at 0 keeps doing so, with a constant of 0
+        let mut start = self.start + r;
+        if self.start == 0 {
+            coefficient.splice(0..0, vec![Expression::zero(); r as usize]);
+            start = 0;
+        }
+        let order = coefficient.len() as u64 - 1;
+        Ok(self.with_coefficients(start, order, coefficient))
+    }
+
+    /// The same series known only up to **order**.
+    pub fn truncate(mut self, order: u64) -> PartialExpansion {
+        if order < self.order {
+            self.order = order;
+            self.coefficient.truncate(order as usize + 1);
+            // the residual of the new order is derived from the source
+            self.residual = None;
+        }
+        self
+    }
+}
+
+impl Neg for PartialExpansion {
+    type Output = PartialExpansion;
+
+    fn neg(self) -> Self::Output {
+        let coefficient = self.coefficient.iter().map(|a| -a.clone()).collect();
+        self.with_coefficients(self.start, self.order, coefficient)
+    }
+}
+
+impl Add for PartialExpansion {
+    type Output = SeriesResult;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (a, b) = self.aligned(rhs)?;
+        let (start, end) = (a.start.min(b.start), a.end().min(b.end()));
+        if end < start {
+            return Err(TaylorExpansionError {
+                err_expr: b.of.clone(),
+                reason: "no term known of the sum",
+            });
+        }
+        let coefficient = (start..=end)
+            .map(|e| a.get_exponent(e) + b.get_exponent(e))
+            .collect();
+        Ok(a.with_coefficients(start, (end - start) as u64, coefficient))
+    }
+}
+
+impl Sub for PartialExpansion {
+    type Output = SeriesResult;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl Mul for PartialExpansion {
+    type Output = SeriesResult;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = self.aligned(rhs)?;
+        let (va, vb) = (a.valuation(), b.valuation());
+        let order = (a.order + vb).min(b.order + va);
+        let coefficient = (0..=order)
+            .map(|k| {
+                (0..=k)
+                    .filter(|j| *j <= a.order && k - j <= b.order)
+                    .map(|j| a.get(j) * b.get(k - j))
+                    .fold(Expression::zero(), |s, t| s + t)
+            })
+            .collect();
+        Ok(a.with_coefficients(a.start + b.start, order, coefficient))
+    }
+}
+
+impl Div for PartialExpansion {
+    type Output = SeriesResult;
+
+    // dividing is multiplying by the reciprocal series
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.recip()?
+    }
+}
+
+#[cfg(test)]
+mod series_arithmetic_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::taylor_expansion::partial_expansion::TaylorExpansion,
+        math_op::{cos::cos, exp::exp, sin::sin},
+    };
+
+    fn coefficients(s: &super::PartialExpansion) -> Vec<String> {
+        s.coefficient.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn arithmetic() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let s = sin(x.clone()).taylor_expansion(&vx, &zero, 5).unwrap();
+        let c = cos(x.clone()).taylor_expansion(&vx, &zero, 5).unwrap();
+        // sin^2 + cos^2 = 1
+        let one = (s.powi(2).unwrap() + c.powi(2).unwrap()).unwrap();
+        assert_eq!(coefficients(&one), ["1", "0", "0", "0", "0", "0"]);
+        let tan = (s.clone() / c).unwrap();
+        assert_eq!(coefficients(&tan), ["0", "1", "0", "1/3", "0", "2/15"]);
+        // x * O(x^6) is only O(x^7) but sin is known up to x^5 alone
+        let sq = (s.clone() * s).unwrap();
+        assert_eq!(sq.order, 6);
+    }
+
+    #[test]
+    fn composition() {
+        let x = Expression::new_variable("x");
+        let u = Expression::new_variable("u");
+        let vx = Variable::new_variable("x");
+        let vu = Variable::new_variable("u");
+        let zero = Expression::zero();
+        let outer = exp(u).taylor_expansion(&vu, &zero, 5).unwrap();
+        let inner = sin(x.clone()).taylor_expansion(&vx, &zero, 5).unwrap();
+        let composed = outer.compose(&inner).unwrap();
+        let direct = exp(sin(x)).taylor_expansion(&vx, &zero, 5).unwrap();
+        assert_eq!(composed.order, 5);
+        assert_eq!(coefficients(&composed), coefficients(&direct));
+    }
+}
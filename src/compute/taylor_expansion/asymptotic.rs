@@ -0,0 +1,181 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{
+        op::{operand::Variable, operator::OperatorType},
+        tree::{AstNode, Expression},
+    },
+    compute::substitute::Substitute,
+};
+
+use super::{
+    laurent::puiseux_expansion,
+    partial_expansion::{PartialExpansion, TaylorExpansionError},
+};
+
+/*
+ * Expansions as x -> +infinity in powers of 1 / x, the Puiseux series
+ * at t = 0+ of the expression with x := 1 / t. The series is returned in
+ * the variable 1 / x so that it prints as c * (1 / x) ^ k + ... + O(...).
+*/
+
+type SeriesResult = Result<PartialExpansion, TaylorExpansionError<Expression>>;
+
+/// **scale** * **series**, where the scale gathers the factors that have
+/// no expansion in powers of 1 / x, e.g. exp(x) or ln(x).
+#[derive(Debug, Clone)]
+pub struct AsymptoticExpansion {
+    pub scale: Expression,
+    pub series: PartialExpansion,
+}
+
+impl Display for AsymptoticExpansion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.scale.is_one() {
+            write!(f, "{}", self.series)
+        } else {
+            write!(f, "{} * ({})", self.scale, self.series)
+        }
+    }
+}
+
+/// Name for the variable t = 1 / **of** that isn't free in **expr**.
+fn reciprocal_variable(expr: &Expression, of: &Variable) -> Variable {
+    let taken =
+        |name: &str| of.name == name || expr.free_variables().iter().any(|v| v.name == name);
+    let mut name = String::from("t");
+    while taken(&name) {
+        name.push('\'');
+    }
+    Variable::new_variable(&name)
+}
+
+/// Series of **expr** in powers of 1 / **of** as **of** -> +infinity, with
+/// every term up to (1 / of)^**order**.
+pub fn inverse_power_expansion(expr: &Expression, of: &Variable, order: i64) -> SeriesResult {
+    let t = reciprocal_variable(expr, of);
+    let reciprocal = Expression::one() / Expression::from(t.clone());
+    let z = expr
+        .clone()
+        .substitute(of, &reciprocal)
+        .map_err(|_| TaylorExpansionError {
+            err_expr: expr.clone(),
+            reason: "substitute failure",
+        })?;
+    let mut series = puiseux_expansion(&z, &t, &Expression::zero(), order)?;
+    series.of = Expression::one() / Expression::from(of.clone());
+    Ok(series)
+}
+
+/// Factors of a product or quotient, the ones in a denominator flagged.
+fn factors(expr: &Expression, inverted: bool, list: &mut Vec<(Expression, bool)>) {
+    match &expr.root {
+        AstNode::Operator(op) if op.descriptor == OperatorType::Mul => {
+            factors(&expr.child[0], inverted, list);
+            factors(&expr.child[1], inverted, list);
+        }
+        AstNode::Operator(op) if op.descriptor == OperatorType::Div => {
+            factors(&expr.child[0], inverted, list);
+            factors(&expr.child[1], !inverted, list);
+        }
+        _ => list.push((expr.clone(), inverted)),
+    }
+}
+
+/// exp(u), ln(u) and their powers.
+fn is_scale(expr: &Expression) -> bool {
+    match &expr.root {
+        AstNode::Operator(op) => match op.descriptor {
+            OperatorType::Exp | OperatorType::Ln => true,
+            OperatorType::Pow => is_scale(&expr.child[0]),
+            _ => false,
+        },
+        AstNode::Operand(_) => false,
+    }
+}
+
+fn product(factors: &[(Expression, bool)]) -> Expression {
+    factors.iter().fold(Expression::one(), |p, (f, inverted)| {
+        match (p.is_one(), inverted) {
+            (true, false) => f.clone(),
+            (_, false) => p * f.clone(),
+            (_, true) => p / f.clone(),
+        }
+    })
+}
+
+/// Asymptotic expansion of **expr** as **of** -> +infinity up to
+/// (1 / of)^**order**. Factors exp(u) and ln(u) without a series in
+/// 1 / **of** are kept aside as the scale, a sum of such terms can't be
+/// expanded.
+pub fn asymptotic_expansion(
+    expr: &Expression,
+    of: &Variable,
+    order: i64,
+) -> Result<AsymptoticExpansion, TaylorExpansionError<Expression>> {
+    if let Ok(series) = inverse_power_expansion(expr, of, order) {
+        return Ok(AsymptoticExpansion {
+            scale: Expression::one(),
+            series,
+        });
+    }
+    let mut list = vec![];
+    factors(expr, false, &mut list);
+    let (series, scale): (Vec<_>, Vec<_>) = list
+        .into_iter()
+        .partition(|(f, _)| inverse_power_expansion(f, of, 0).is_ok());
+    if scale.is_empty() || !scale.iter().all(|(f, _)| is_scale(f)) {
+        return Err(TaylorExpansionError {
+            err_expr: expr.clone(),
+            reason: "no expansion in powers of the reciprocal",
+        });
+    }
+    Ok(AsymptoticExpansion {
+        scale: product(&scale),
+        series: inverse_power_expansion(&product(&series), of, order)?,
+    })
+}
+
+#[cfg(test)]
+mod asymptotic_expansion_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{exp::exp, ln::ln, pow::Pow},
+    };
+
+    use super::{asymptotic_expansion, inverse_power_expansion};
+
+    fn terms(s: &super::PartialExpansion) -> Vec<String> {
+        s.coefficient.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn inverse_powers() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let f = ln(Expression::one() + Expression::one() / x.clone());
+        let s = inverse_power_expansion(&f, &vx, 3).unwrap();
+        assert_eq!(s.start, 1);
+        assert_eq!(terms(&s), ["1", "-1/2", "1/3"]);
+        // sqrt(x^2 + 1) = x + 1 / (2 x) + ...
+        let half = Expression::one() / Expression::from(2);
+        let f = (x.clone().pow(2) + Expression::one()).pow(half);
+        let s = inverse_power_expansion(&f, &vx, 1).unwrap();
+        assert_eq!(s.start, -1);
+        assert_eq!(terms(&s), ["1", "0", "1/2"]);
+    }
+
+    #[test]
+    fn with_scale() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let f = exp(x.clone()) / (x.clone() + Expression::one());
+        let e = asymptotic_expansion(&f, &vx, 3).unwrap();
+        assert_eq!(e.scale, exp(x.clone()));
+        assert_eq!(e.series.start, 1);
+        assert_eq!(terms(&e.series), ["1", "-1", "1"]);
+        assert!(asymptotic_expansion(&(exp(x.clone()) + x), &vx, 2).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{
+        op::{
+            operand::{AstOperand, Variable},
+            operator::OperatorType,
+        },
+        tree::{AstNode, Expression},
+    },
+    compute::num_aggregate::NumAggregate,
+    math_op::{cos::cos, exp::exp, ln::ln, pow::Pow, sin::sin},
+    smart_num::rational::ToRational,
+};
+
+use super::{
+    partial_expansion::{PartialExpansion, TaylorExpansion, TaylorExpansionError},
+    recurrence::has_recurrence,
+};
+
+/*
+ * Laurent and Puiseux series, built bottom-up with series arithmetic.
+ * Subexpressions analytic at the point are expanded as Taylor series,
+ * quotients pick up negative exponents through `recip`, and
+ * u ^ (p / q) = c^(p / q) (of - at)^(p e / q) (u / (c (of - at)^e))^(p / q)
+ * for u ~ c (of - at)^e brings in fractional ones. Fractional powers are
+ * taken on the right of the point, where (of - at) > 0.
+*/
+
+type SeriesResult = Result<PartialExpansion, TaylorExpansionError<Expression>>;
+
+/// Rounds of raising the working order before giving up on reaching
+/// the requested exponent, every division or cancellation loses terms.
+const MAX_ROUNDS: u64 = 4;
+
+impl PartialExpansion {
+    /// Coefficient of (of - at)^-1, None when the series isn't known
+    /// that far.
+    pub fn residue(&self) -> Option<Expression> {
+        let e = -(self.ramification as i64);
+        if self.end() < e {
+            None
+        } else {
+            Some(self.get_exponent(e))
+        }
+    }
+}
+
+/// Series of **expr** around **of** = **at** with every term up to
+/// (of - at)^**order**, exponents may be negative or fractional.
+pub fn puiseux_expansion(
+    expr: &Expression,
+    of: &Variable,
+    at: &Expression,
+    order: i64,
+) -> SeriesResult {
+    let mut n = order.max(0) as u64 + 2;
+    for _ in 0..MAX_ROUNDS {
+        let s = series_of(expr, of, at, n)?.normalize();
+        let target = order * s.ramification as i64;
+        if s.end() >= target {
+            let keep = (target - s.start).max(0) as u64;
+            return Ok(s.truncate(keep));
+        }
+        n += (target - s.end()) as u64 + 1;
+    }
+    Err(TaylorExpansionError {
+        err_expr: expr.clone(),
+        reason: "too many terms lost to cancellation",
+    })
+}
+
+/// Like `puiseux_expansion`, for series with integer exponents only.
+pub fn laurent_expansion(
+    expr: &Expression,
+    of: &Variable,
+    at: &Expression,
+    order: i64,
+) -> SeriesResult {
+    let s = puiseux_expansion(expr, of, at, order)?;
+    if s.ramification != 1 {
+        return Err(TaylorExpansionError {
+            err_expr: expr.clone(),
+            reason: "a branch point",
+        });
+    }
+    Ok(s)
+}
+
+/// Residue of **expr** at **of** = **at**, the coefficient of
+/// (of - at)^-1 in its Laurent series.
+pub fn residue(
+    expr: &Expression,
+    of: &Variable,
+    at: &Expression,
+) -> Result<Expression, TaylorExpansionError<Expression>> {
+    laurent_expansion(expr, of, at, -1)?
+        .residue()
+        .ok_or(TaylorExpansionError {
+            err_expr: expr.clone(),
+            reason: "too many terms lost to cancellation",
+        })
+}
+
+/// Series of **expr** with its analytic leaves expanded to order **n**.
+fn series_of(expr: &Expression, of: &Variable, at: &Expression, n: u64) -> SeriesResult {
+    if has_recurrence(expr, of) {
+        if let Ok(s) = expr.clone().taylor_expansion(of, at, n) {
+            return Ok(s);
+        }
+    }
+    let op = match &expr.root {
+        AstNode::Operator(op) => &op.descriptor,
+        AstNode::Operand(_) => return expr.clone().taylor_expansion(of, at, n),
+    };
+    let child = &expr.child;
+    let sub = |idx: usize| series_of(&child[idx], of, at, n);
+    match op {
+        OperatorType::Neg => Ok(-sub(0)?),
+        OperatorType::Add => sub(0)? + sub(1)?,
+        OperatorType::Sub => sub(0)? - sub(1)?,
+        OperatorType::Mul => sub(0)? * sub(1)?,
+        OperatorType::Div => sub(0)? / sub(1)?,
+        OperatorType::Pow if child[1].contains_variable(of) => {
+            let power = exp(child[1].clone() * ln(child[0].clone()));
+            series_of(&power, of, at, n)
+        }
+        OperatorType::Pow => power_series(sub(0)?, &child[1].clone().num_aggregate(), n),
+        OperatorType::Exp => apply(exp, sub(0)?, n),
+        OperatorType::Ln => apply(ln, sub(0)?, n),
+        OperatorType::Sin => apply(sin, sub(0)?, n),
+        OperatorType::Cos => apply(cos, sub(0)?, n),
+        _ => Err(TaylorExpansionError {
+            err_expr: expr.clone(),
+            reason: "no series for this operator",
+        }),
+    }
+}
+
+/// Series of f(u) from the Taylor series of f at the constant term of u.
+fn apply(f: fn(Expression) -> Expression, u: PartialExpansion, n: u64) -> SeriesResult {
+    let t = Variable::new_variable("t");
+    let u0 = u.trim().get_exponent(0);
+    let outer = f(Expression::from(t.clone())).taylor_expansion(&t, &u0, n)?;
+    outer.compose(&u)
+}
+
+/// Series of u ^ **a** for a constant **a**.
+fn power_series(u: PartialExpansion, a: &Expression, n: u64) -> SeriesResult {
+    if let AstNode::Operand(AstOperand::Num(k)) = &a.root {
+        if let Some(k) = k.to_i64() {
+            return u.powi(k);
+        }
+    }
+    // u = c (of - at)^(e / r) (1 + w)
+    let u = u.trim();
+    let (c, e, r) = (u.get_exponent(u.start), u.start, u.ramification);
+    let mut unit = u.scale(Expression::one() / c.clone());
+    unit.start = 0;
+    let t = Variable::new_variable("t");
+    let outer = Expression::from(t.clone())
+        .pow(a.clone())
+        .taylor_expansion(&t, &Expression::one(), n)?;
+    let mut power = outer.compose(&unit)?.scale(c.pow(a.clone()));
+    if e == 0 {
+        return Ok(power);
+    }
+    // (of - at)^(a e / r) with a = p / q
+    let rational = match &a.root {
+        AstNode::Operand(AstOperand::Num(k)) if k.is_exact() => k.to_rational(),
+        _ => None,
+    };
+    let a = rational.ok_or(TaylorExpansionError {
+        err_expr: a.clone(),
+        reason: "a symbolic power of a vanishing base",
+    })?;
+    power = power.ramify(r * a.denominator);
+    power.start = a.sign * a.nominator as i64 * e;
+    Ok(power)
+}
+
+#[cfg(test)]
+mod laurent_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{cos::cos, exp::exp, pow::Pow, sin::sin},
+    };
+
+    use super::{laurent_expansion, puiseux_expansion, residue};
+
+    fn terms(s: &super::PartialExpansion) -> Vec<String> {
+        s.coefficient.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn laurent_series() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let s = laurent_expansion(&(Expression::one() / x.clone()), &vx, &zero, 2).unwrap();
+        assert_eq!(s.start, -1);
+        assert_eq!(terms(&s), ["1", "0", "0", "0"]);
+        let f = sin(x.clone()) / x.clone().pow(3);
+        let s = laurent_expansion(&f, &vx, &zero, 2).unwrap();
+        assert_eq!(s.start, -2);
+        assert_eq!(terms(&s), ["1", "0", "-1/6", "0", "1/120"]);
+        assert_eq!(s.to_string(), "x ^ -2 + -1/6 + 1/120 * x ^ 2 + O(x ^ 3)");
+    }
+
+    #[test]
+    fn residues() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let f = exp(x.clone()) / x.clone().pow(2);
+        assert_eq!(residue(&f, &vx, &zero).unwrap().to_string(), "1");
+        let cot = cos(x.clone()) / sin(x.clone());
+        assert_eq!(residue(&cot, &vx, &zero).unwrap().to_string(), "1");
+        let f = Expression::one() / (x.clone().pow(2) - Expression::one());
+        let r = residue(&f, &vx, &Expression::one()).unwrap();
+        assert_eq!(r.to_string(), "1/2");
+        assert!(residue(&sin(x.clone()), &vx, &zero).unwrap().is_zero());
+    }
+
+    #[test]
+    fn puiseux_series() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let half = Expression::one() / Expression::from(2);
+        let f = (x.clone() + x.clone().pow(2)).pow(half.clone());
+        let s = puiseux_expansion(&f, &vx, &zero, 2).unwrap();
+        assert_eq!((s.start, s.ramification), (1, 2));
+        assert_eq!(terms(&s), ["1", "0", "1/2", "0"]);
+        // sqrt(x^2) = x, the fractional exponents cancel
+        let f = x.clone().pow(2).pow(half.clone());
+        let s = laurent_expansion(&f, &vx, &zero, 3).unwrap();
+        assert_eq!((s.start, terms(&s)[0].as_str()), (1, "1"));
+        assert!(laurent_expansion(&x.clone().pow(half), &vx, &zero, 1).is_err());
+    }
+}
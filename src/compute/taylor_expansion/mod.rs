@@ -1 +1,8 @@
+pub mod arithmetic;
+pub mod asymptotic;
+pub mod laurent;
+pub mod multivariate;
+pub mod pade;
 pub mod partial_expansion;
+pub mod recurrence;
+pub mod remainder;
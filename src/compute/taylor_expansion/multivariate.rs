@@ -0,0 +1,176 @@
+#![allow(dead_code)]
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::{evaluate::is_zero_expr, num_aggregate::NumAggregate},
+    math_op::pow::Pow,
+};
+
+use super::partial_expansion::{TaylorExpansion, TaylorExpansionError};
+
+/// Expansion in several variables up to total degree **order**. The
+/// coefficient of (of[0] - at[0])^a0 * ... * (of[n] - at[n])^an is stored
+/// under the multi-index [a0, ..., an], vanishing ones are left out.
+#[derive(Debug, Clone)]
+pub struct MultivariateExpansion {
+    pub order: u64,
+    pub of: Vec<Variable>,
+    pub at: Vec<Expression>,
+    pub coefficient: BTreeMap<Vec<u64>, Expression>,
+}
+
+impl MultivariateExpansion {
+    /// Coefficient of the monomial with exponents **index**.
+    pub fn get(&self, index: &[u64]) -> Expression {
+        self.coefficient
+            .get(index)
+            .cloned()
+            .unwrap_or(Expression::zero())
+    }
+
+    /// Multi-indices with a non-zero coefficient, by increasing total
+    /// degree and then by decreasing powers of the first variables.
+    fn graded_indices(&self) -> Vec<&Vec<u64>> {
+        let mut indices: Vec<&Vec<u64>> = self.coefficient.keys().collect();
+        indices.sort_by_key(|a| (a.iter().sum::<u64>(), std::cmp::Reverse(*a)));
+        indices
+    }
+
+    fn monomial(&self, index: &[u64]) -> Option<Expression> {
+        index
+            .iter()
+            .zip(self.of.iter().zip(&self.at))
+            .filter(|(k, _)| **k > 0)
+            .map(|(k, (v, a))| {
+                let h = (Expression::from(v.clone()) - a.clone()).num_aggregate();
+                if *k == 1 {
+                    h
+                } else {
+                    h.pow(*k)
+                }
+            })
+            .reduce(|a, b| a * b)
+    }
+
+    fn term(&self, index: &[u64]) -> Expression {
+        let c = self.coefficient[index].clone();
+        match self.monomial(index) {
+            None => c,
+            Some(m) if c.is_one() => m,
+            Some(m) => c * m,
+        }
+    }
+
+    /// The polynomial approximation, the sum of every term.
+    pub fn to_expression(&self) -> Expression {
+        self.graded_indices()
+            .into_iter()
+            .map(|index| self.term(index))
+            .reduce(|a, b| a + b)
+            .unwrap_or(Expression::zero())
+    }
+}
+
+impl Display for MultivariateExpansion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for index in self.graded_indices() {
+            write!(f, "{} + ", self.term(index))?;
+        }
+        write!(f, "O(|h|^{})", self.order + 1)
+    }
+}
+
+/// Expansion of **expr** around **of** = **at** up to total degree
+/// **order**. The variables are expanded one after the other, each
+/// coefficient in the first being expanded in the rest up to the degree
+/// left over.
+pub fn multivariate_taylor_expansion(
+    expr: &Expression,
+    of: &[Variable],
+    at: &[Expression],
+    order: u64,
+) -> Result<MultivariateExpansion, TaylorExpansionError<Expression>> {
+    if of.len() != at.len() {
+        return Err(TaylorExpansionError {
+            err_expr: expr.clone(),
+            reason: "as many variables as coordinates needed",
+        });
+    }
+    let mut coefficient = BTreeMap::new();
+    expand_into(expr, of, at, order, &mut vec![], &mut coefficient)?;
+    Ok(MultivariateExpansion {
+        order,
+        of: of.to_vec(),
+        at: at.to_vec(),
+        coefficient,
+    })
+}
+
+fn expand_into(
+    expr: &Expression,
+    of: &[Variable],
+    at: &[Expression],
+    order: u64,
+    prefix: &mut Vec<u64>,
+    coefficient: &mut BTreeMap<Vec<u64>, Expression>,
+) -> Result<(), TaylorExpansionError<Expression>> {
+    if of.is_empty() {
+        let c = expr.clone().num_aggregate();
+        if !is_zero_expr(&c) {
+            coefficient.insert(prefix.clone(), c);
+        }
+        return Ok(());
+    }
+    let expansion = expr.clone().taylor_expansion(&of[0], &at[0], order)?;
+    for (k, c) in expansion.coefficient.iter().enumerate() {
+        if c.is_zero() {
+            continue;
+        }
+        prefix.push(k as u64);
+        expand_into(c, &of[1..], &at[1..], order - k as u64, prefix, coefficient)?;
+        prefix.pop();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod multivariate_expansion_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{exp::exp, sin::sin},
+    };
+
+    use super::multivariate_taylor_expansion;
+
+    #[test]
+    fn second_order() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let of = [Variable::new_variable("x"), Variable::new_variable("y")];
+        let origin = [Expression::zero(), Expression::zero()];
+        let f = exp(x.clone()) * sin(y.clone());
+        let e = multivariate_taylor_expansion(&f, &of, &origin, 3).unwrap();
+        assert_eq!(e.get(&[0, 1]).to_string(), "1");
+        assert_eq!(e.get(&[1, 1]).to_string(), "1");
+        assert_eq!(e.get(&[2, 1]).to_string(), "1/2");
+        assert_eq!(e.get(&[0, 3]).to_string(), "-1/6");
+        assert!(e.get(&[1, 0]).is_zero());
+        assert_eq!(e.coefficient.len(), 4);
+    }
+
+    #[test]
+    fn linearisation() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let of = [Variable::new_variable("x"), Variable::new_variable("y")];
+        let at = [Expression::one(), Expression::from(2)];
+        let f = x.clone() * x.clone() * y.clone();
+        let e = multivariate_taylor_expansion(&f, &of, &at, 1).unwrap();
+        assert_eq!(e.get(&[0, 0]).to_string(), "2");
+        assert_eq!(e.get(&[1, 0]).to_string(), "4");
+        assert_eq!(e.get(&[0, 1]).to_string(), "1");
+        assert_eq!(e.to_expression().to_string(), "2 + 4 * (x - 1) + y - 2");
+    }
+}
@@ -0,0 +1,190 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{
+        op::operand::{AstOperand, Variable},
+        tree::{AstNode, Expression},
+    },
+    compute::num_aggregate::NumAggregate,
+    math_op::pow::Pow,
+    smart_num::rational::{RationalNum, ToRational},
+};
+
+use super::partial_expansion::{PartialExpansion, TaylorExpansion, TaylorExpansionError};
+
+/*
+ * Padé approximants P / Q with deg P <= m, deg Q <= n and Q(at) = 1,
+ * matching the Taylor series up to (of - at)^(m + n). Writing c_k for
+ * the Taylor coefficients, the coefficients q_j of Q solve
+ *   q_1 c_(k-1) + ... + q_n c_(k-n) = -c_k   for k in m+1..=m+n,
+ * and then p_i = q_0 c_i + ... + q_i c_0, with c_k = 0 for k < 0.
+*/
+
+type PadeResult<T> = Result<T, TaylorExpansionError<Expression>>;
+
+fn overflow() -> TaylorExpansionError<Expression> {
+    TaylorExpansionError {
+        err_expr: Expression::zero(),
+        reason: "rational overflow",
+    }
+}
+
+fn to_rational(c: &Expression) -> PadeResult<RationalNum> {
+    match &c.root {
+        AstNode::Operand(AstOperand::Num(k)) if k.is_exact() => k.to_rational(),
+        _ => None,
+    }
+    .ok_or(TaylorExpansionError {
+        err_expr: c.clone(),
+        reason: "a coefficient that isn't rational",
+    })
+}
+
+/// Solves **a** x = **b** exactly by Gaussian elimination, failing when
+/// the matrix is singular. Errors report **of**, the series variable.
+fn solve(
+    mut a: Vec<Vec<RationalNum>>,
+    mut b: Vec<RationalNum>,
+    of: &Expression,
+) -> PadeResult<Vec<RationalNum>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .find(|r| a[*r][col].nominator != 0)
+            .ok_or(TaylorExpansionError {
+                err_expr: of.clone(),
+                reason: "a singular system for the denominator",
+            })?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for r in 0..n {
+            if r == col || a[r][col].nominator == 0 {
+                continue;
+            }
+            let f = a[r][col].checked_div(a[col][col]).ok_or_else(overflow)?;
+            let pivot_row = a[col].clone();
+            for (x, p) in a[r].iter_mut().zip(pivot_row).skip(col) {
+                *x = x
+                    .checked_sub(f.checked_mul(p).ok_or_else(overflow)?)
+                    .ok_or_else(overflow)?;
+            }
+            let d = f.checked_mul(b[col]).ok_or_else(overflow)?;
+            b[r] = b[r].checked_sub(d).ok_or_else(overflow)?;
+        }
+    }
+    (0..n)
+        .map(|i| b[i].checked_div(a[i][i]).ok_or_else(overflow))
+        .collect()
+}
+
+impl PartialExpansion {
+    /// The [**m** / **n**] Padé approximant of a Taylor series with exact
+    /// rational coefficients known up to order m + n.
+    pub fn pade(&self, m: u64, n: u64) -> PadeResult<Expression> {
+        if self.start != 0 || self.ramification != 1 || self.order < m + n {
+            return Err(TaylorExpansionError {
+                err_expr: self.of.clone(),
+                reason: "a Taylor series known up to order m + n needed",
+            });
+        }
+        let c = (0..=(m + n) as usize)
+            .map(|k| to_rational(&self.coefficient[k]))
+            .collect::<PadeResult<Vec<RationalNum>>>()?;
+        let c_k = |k: i64| {
+            if k < 0 {
+                RationalNum::from(0_i64)
+            } else {
+                c[k as usize]
+            }
+        };
+        let (m, n) = (m as i64, n as i64);
+        let a = (m + 1..=m + n)
+            .map(|k| (1..=n).map(|j| c_k(k - j)).collect())
+            .collect();
+        let b = (m + 1..=m + n).map(|k| -c_k(k)).collect();
+        let mut q = vec![RationalNum::from(1_i64)];
+        q.extend(solve(a, b, &self.of)?);
+        let p = (0..=m)
+            .map(|i| {
+                (0..=i.min(n)).try_fold(RationalNum::from(0_i64), |s, j| {
+                    s.checked_add(q[j as usize].checked_mul(c_k(i - j))?)
+                })
+            })
+            .collect::<Option<Vec<RationalNum>>>()
+            .ok_or_else(overflow)?;
+        let h = (self.of.clone() - self.at.clone()).num_aggregate();
+        Ok(polynomial(&p, &h) / polynomial(&q, &h))
+    }
+}
+
+/// Sum of **c[k]** * **h**^k, leaving out the vanishing terms.
+fn polynomial(c: &[RationalNum], h: &Expression) -> Expression {
+    c.iter()
+        .enumerate()
+        .filter(|(_, c)| c.nominator != 0)
+        .map(|(k, c)| {
+            let c = Expression::from(*c);
+            let power = match k {
+                0 => return c,
+                1 => h.clone(),
+                _ => h.clone().pow(k as i64),
+            };
+            if c.is_one() {
+                power
+            } else {
+                c * power
+            }
+        })
+        .reduce(|a, b| a + b)
+        .unwrap_or(Expression::zero())
+}
+
+/// The [**m** / **n**] Padé approximant of **expr** around **var** =
+/// **at**, a quotient of polynomials in (var - at).
+pub fn pade(
+    expr: &Expression,
+    var: &Variable,
+    at: &Expression,
+    m: u64,
+    n: u64,
+) -> PadeResult<Expression> {
+    expr.clone().taylor_expansion(var, at, m + n)?.pade(m, n)
+}
+
+#[cfg(test)]
+mod pade_tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::evaluate::Evaluate,
+        math_op::{cos::cos, exp::exp, ln::ln},
+    };
+
+    use super::pade;
+
+    #[test]
+    fn approximants() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let r = pade(&exp(x.clone()), &vx, &zero, 1, 1).unwrap();
+        assert_eq!(r.to_string(), "(1 + 1/2 * x) / (1 + -1/2 * x)");
+        // ln(1 + x) at x = 1 is far better than its Taylor polynomial
+        let f = ln(Expression::one() + x.clone());
+        let r = pade(&f, &vx, &zero, 2, 2).unwrap();
+        let bindings = HashMap::from([(String::from("x"), 1.0)]);
+        let v = r.evaluate(&bindings).unwrap();
+        assert!((v - 2_f64.ln()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn singular_system() {
+        // cos is even, the [1 / 1] system 0 * q_1 = -c_2 has no solution
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let err = pade(&cos(x.clone()), &vx, &Expression::zero(), 1, 1).unwrap_err();
+        assert_eq!(err.reason, "a singular system for the denominator");
+        assert_eq!(err.err_expr, x);
+    }
+}
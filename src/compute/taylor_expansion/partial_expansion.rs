@@ -4,34 +4,119 @@ use std::{collections::VecDeque, fmt::Display};
 
 use crate::{
     ast::{op::operand::Variable, tree::Expression},
-    compute::{derivative::Derivative, num_aggregate::NumAggregate, substitute::Substitute},
+    compute::{
+        derivative::cache::DerivativeCache, evaluate::Evaluate, num_aggregate::NumAggregate,
+        substitute::Substitute,
+    },
     math_op::pow::Pow,
     smart_num::SmartNum,
 };
 
+use super::recurrence::{has_recurrence, taylor_coefficients};
+
+/// Coefficients of (of - at)^((start + k) / ramification) for k up to
+/// **order**, the rest is O((of - at)^((start + order + 1) / ramification)).
+/// Taylor series start at 0 with a ramification of 1, Laurent series
+/// start below 0 and Puiseux series have fractional exponents.
 #[derive(Debug, Clone)]
 pub struct PartialExpansion {
     pub order: u64,
     pub of: Expression,
     pub at: Expression,
     pub coefficient: VecDeque<Expression>,
-    pub residual: Expression,
+    pub start: i64,
+    pub ramification: u64,
+    /// The (order + 1)-th derivative, only kept by expansions built by
+    /// repeated differentiation, see `derive_residual` for the others.
+    pub residual: Option<Expression>,
+    /// The function a Taylor series was expanded from.
+    pub source: Option<Expression>,
+}
+
+impl PartialExpansion {
+    /// (of - at)^((start + **k**) / ramification), None for the power 0.
+    fn power(&self, k: i64) -> Option<Expression> {
+        let b = (self.of.clone() - self.at.clone()).num_aggregate();
+        let e = (Expression::from(self.start + k) / Expression::from(self.ramification))
+            .num_aggregate();
+        if e.is_zero() {
+            None
+        } else if e.is_one() {
+            Some(b)
+        } else {
+            Some(b.pow(e))
+        }
+    }
+
+    /// The k-th term, None when its coefficient vanishes.
+    fn term(&self, k: usize) -> Option<Expression> {
+        let c = &self.coefficient[k];
+        if c.is_zero() {
+            return None;
+        }
+        Some(match self.power(k as i64) {
+            None => c.clone(),
+            Some(p) if c.is_one() => p,
+            Some(p) => c.clone() * p,
+        })
+    }
+
+    /// The truncated series, the sum of every known term.
+    pub fn to_expression(&self) -> Expression {
+        (0..self.coefficient.len())
+            .filter_map(|k| self.term(k))
+            .reduce(|a, b| a + b)
+            .unwrap_or(Expression::zero())
+    }
+
+    /// The (order + 1)-th derivative of the source, computed when the
+    /// expansion didn't keep it, e.g. one built from recurrences.
+    pub fn derive_residual(&self) -> Option<Expression> {
+        if let Some(residual) = &self.residual {
+            return Some(residual.clone());
+        }
+        let of: Option<Variable> = self.of.clone().into();
+        DerivativeCache::new()
+            .nth_derivative(self.source.as_ref()?, &of?, self.order + 1)
+            .ok()
+    }
+
+    /// Coefficient of (of - at)^**k**, None beyond the order.
+    pub fn coefficient(&self, k: i64) -> Option<Expression> {
+        let e = k * self.ramification as i64;
+        if e > self.start + self.order as i64 {
+            None
+        } else if e < self.start {
+            Some(Expression::zero())
+        } else {
+            Some(self.coefficient[(e - self.start) as usize].clone())
+        }
+    }
 }
 
 impl Display for PartialExpansion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut content = String::from("");
-        let b = (self.of.clone() - self.at.clone()).num_aggregate();
-        for (idx, term) in self.coefficient.iter().enumerate() {
-            if term.is_zero() {
-                continue;
+        for k in 0..self.coefficient.len() {
+            if let Some(term) = self.term(k) {
+                write!(f, "{} + ", term)?;
             }
-            content.push_str(format!("{} * ", term).as_str());
-            content.push_str(format!("{}", b.clone().pow(idx as i64)).as_str());
-            content.push_str(" + ")
         }
-        content.push_str(format!("O({})", b.clone().pow(self.order + 1),).as_str());
-        write!(f, "{}", content)
+        match self.power(self.order as i64 + 1) {
+            None => write!(f, "O(1)"),
+            Some(p) => write!(f, "O({})", p),
+        }
+    }
+}
+
+impl<T> Evaluate<T> for PartialExpansion
+where
+    Expression: Evaluate<T>,
+{
+    type Output = <Expression as Evaluate<T>>::Output;
+
+    /// Value of the truncated series, see `to_expression`.
+    fn evaluate(&self, bindings: T) -> Self::Output {
+        self.to_expression().evaluate(bindings)
     }
 }
 
@@ -42,8 +127,8 @@ pub trait TaylorExpansion<'a, T, U> {
 
 #[derive(Debug)]
 pub struct TaylorExpansionError<T = Expression> {
-    err_expr: T,
-    reason: &'static str,
+    pub(crate) err_expr: T,
+    pub(crate) reason: &'static str,
 }
 
 impl<T> Display for TaylorExpansionError<T>
@@ -58,10 +143,31 @@ where
 impl<'a> TaylorExpansion<'a, &Variable, &Expression> for Expression {
     type Output = Result<PartialExpansion, TaylorExpansionError<Expression>>;
 
+    /// Coefficients come from the recurrences of `recurrence` when every
+    /// operator has one, and from repeated differentiation otherwise.
     fn taylor_expansion(self, of: &Variable, at: &Expression, order: u64) -> Self::Output {
+        if has_recurrence(&self, of) {
+            let coefficient = taylor_coefficients(&self, of, at)?
+                .take(order as usize + 1)
+                .collect();
+            // the residual is left to derive_residual, differentiating
+            // order + 1 times is what the recurrences avoid
+            return Ok(PartialExpansion {
+                order,
+                of: Expression::from(of.clone()),
+                at: at.clone(),
+                coefficient,
+                start: 0,
+                ramification: 1,
+                residual: None,
+                source: Some(self),
+            });
+        }
+        let source = self.clone();
         let mut coefficient: VecDeque<Expression> = VecDeque::new();
         let mut residual = self;
         let mut factorial = SmartNum::from(1_i64);
+        let mut cache = DerivativeCache::new();
         let err_expr = residual.clone();
         for k in 0..=order {
             factorial = factorial * SmartNum::from(k.max(1_u64));
@@ -76,17 +182,22 @@ impl<'a> TaylorExpansion<'a, &Variable, &Expression> for Expression {
                 / Expression::from(factorial);
             coefficient.push_back(cur.num_aggregate());
 
-            residual = residual.derivative(of).map_err(|_| TaylorExpansionError {
-                err_expr: err_expr.clone(),
-                reason: "derivative failure",
-            })?;
+            residual = cache
+                .derivative(&residual, of)
+                .map_err(|_| TaylorExpansionError {
+                    err_expr: err_expr.clone(),
+                    reason: "derivative failure",
+                })?;
         }
         return Ok(PartialExpansion {
             order,
             of: Expression::from(of.clone()),
             at: Expression::from(at.clone()),
             coefficient,
-            residual,
+            start: 0,
+            ramification: 1,
+            residual: Some(residual),
+            source: Some(source),
         });
     }
 }
@@ -110,7 +221,11 @@ impl<'a> TaylorExpansion<'a, Expression, Expression> for Expression {
 
 #[cfg(test)]
 mod taylor_expansion_test {
-    use crate::{ast::tree::Expression, math_op::sin::sin};
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::{evaluate::Evaluate, taylor_expansion::laurent::laurent_expansion},
+        math_op::{cos::cos, exp::exp, sin::sin},
+    };
 
     use super::TaylorExpansion;
 
@@ -123,4 +238,47 @@ mod taylor_expansion_test {
         let expansion = y.taylor_expansion(x, zero, 10).unwrap();
         println!("{}", expansion);
     }
+
+    #[test]
+    fn conversion() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let s = exp(x.clone()).taylor_expansion(&vx, &zero, 2).unwrap();
+        assert_eq!(s.coefficient(2).unwrap().to_string(), "1/2");
+        assert!(s.coefficient(3).is_none());
+        let s = cos(x.clone()).taylor_expansion(&vx, &zero, 4).unwrap();
+        assert_eq!(s.to_string(), "1 + -1/2 * x ^ 2 + 1/24 * x ^ 4 + O(x ^ 5)");
+        assert_eq!(
+            s.to_expression().to_string(),
+            "1 + -1/2 * x ^ 2 + 1/24 * x ^ 4"
+        );
+        let v = s.evaluate((&vx, 0.5)).unwrap();
+        assert!((v - (1.0 - 0.125 + 0.0625 / 24.0)).abs() < 1e-15);
+        let s = laurent_expansion(&(Expression::one() / x), &vx, &zero, 0).unwrap();
+        assert_eq!(s.to_string(), "x ^ -1 + O(x)");
+    }
+
+    #[test]
+    fn termwise_calculus() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let s = sin(x.clone()).taylor_expansion(&vx, &zero, 5).unwrap();
+        let d = s.derivative().unwrap();
+        let c = cos(x.clone()).taylor_expansion(&vx, &zero, 4).unwrap();
+        assert_eq!((d.start, d.order), (0, 4));
+        assert_eq!(d.to_string(), c.to_string());
+        let i = c.integral().unwrap();
+        assert_eq!((i.start, i.order), (0, 5));
+        assert_eq!(i.to_string(), s.to_string());
+        // 1 / x^2 integrates to -1 / x but 1 / x to a logarithm
+        let f = Expression::one() / x.clone();
+        let s = laurent_expansion(&(f.clone() * f.clone()), &vx, &zero, 1).unwrap();
+        let i = s.integral().unwrap();
+        assert_eq!(i.coefficient(-1).unwrap().to_string(), "-1");
+        assert_eq!(i.end(), s.end() + 1);
+        let s = laurent_expansion(&f, &vx, &zero, 1).unwrap();
+        assert!(s.integral().is_err());
+    }
 }
@@ -0,0 +1,456 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{
+        op::{
+            operand::{AstOperand, Variable},
+            operator::OperatorType,
+        },
+        tree::{AstNode, Expression},
+    },
+    compute::{evaluate::is_zero_expr, num_aggregate::NumAggregate},
+    math_op::{cos::cos, exp::exp, ln::ln, pow::Pow, sin::sin},
+};
+
+use super::partial_expansion::TaylorExpansionError;
+
+/*
+ * Taylor coefficients from recurrences on truncated power series rather
+ * than repeated differentiation. The expression is flattened into a list
+ * of nodes, children first, and the k-th coefficient of every node is
+ * computed from the coefficients up to k of its children, e.g. for
+ * w = exp(u), w' = u' w gives k w_k = sum of j u_j w_(k-j) for j in 1..=k.
+ * Each coefficient costs O(k) operations per node, where differentiating
+ * the whole expression k times grows the tree exponentially.
+*/
+
+#[derive(Debug, Clone)]
+enum Recurrence {
+    /// Does not depend on the variable.
+    Constant(Expression),
+    /// The variable itself, at + (of - at).
+    Identity,
+    Neg(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Exp(usize),
+    Ln(usize),
+    /// sin(u), with the coefficients of cos(u) alongside.
+    Sin(usize),
+    /// cos(u), with the coefficients of sin(u) alongside.
+    Cos(usize),
+    /// u ^ a for a constant a that isn't a non-negative integer.
+    Power(usize, Expression),
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    recurrence: Recurrence,
+    coefficient: Vec<Expression>,
+    /// The other half of a sin / cos pair, empty otherwise.
+    companion: Vec<Expression>,
+}
+
+/// Lazy iterator over the Taylor coefficients of an expression, the
+/// k-th item is the coefficient of (of - at)^k. Never ends.
+#[derive(Debug, Clone)]
+pub struct TaylorCoefficients {
+    nodes: Vec<Node>,
+    at: Expression,
+    next: usize,
+}
+
+/// Whether every operator of **expr** depending on **of** has a
+/// recurrence, the others are left to repeated differentiation.
+pub fn has_recurrence(expr: &Expression, of: &Variable) -> bool {
+    if !expr.contains_variable(of) {
+        return true;
+    }
+    match &expr.root {
+        AstNode::Operand(_) => true,
+        AstNode::Operator(op) => {
+            matches!(
+                op.descriptor,
+                OperatorType::Neg
+                    | OperatorType::Add
+                    | OperatorType::Sub
+                    | OperatorType::Mul
+                    | OperatorType::Div
+                    | OperatorType::Exp
+                    | OperatorType::Ln
+                    | OperatorType::Sin
+                    | OperatorType::Cos
+                    | OperatorType::Pow
+            ) && expr.child.iter().all(|c| has_recurrence(c, of))
+        }
+    }
+}
+
+/// Coefficients of **expr** around **of** = **at**. Fails when an operator
+/// has no recurrence or **expr** is not analytic there, e.g. ln(u) or
+/// 1 / u with u vanishing at **at**.
+pub fn taylor_coefficients(
+    expr: &Expression,
+    of: &Variable,
+    at: &Expression,
+) -> Result<TaylorCoefficients, TaylorExpansionError<Expression>> {
+    let mut coefficients = TaylorCoefficients {
+        nodes: vec![],
+        at: at.clone(),
+        next: 0,
+    };
+    coefficients.push_expr(expr, of)?;
+    Ok(coefficients)
+}
+
+fn not_analytic(expr: &Expression) -> TaylorExpansionError<Expression> {
+    TaylorExpansionError {
+        err_expr: expr.clone(),
+        reason: "a singularity at the expansion point",
+    }
+}
+
+impl TaylorCoefficients {
+    /// Flattens **expr** into nodes and returns the index of its root.
+    /// The constant coefficient of every node is computed on the way, so
+    /// that singular points are reported here rather than mid-iteration.
+    fn push_expr(
+        &mut self,
+        expr: &Expression,
+        of: &Variable,
+    ) -> Result<usize, TaylorExpansionError<Expression>> {
+        if !expr.contains_variable(of) {
+            return Ok(self.push(Recurrence::Constant(expr.clone())));
+        }
+        let op = match &expr.root {
+            AstNode::Operand(_) => return Ok(self.push(Recurrence::Identity)),
+            AstNode::Operator(op) => &op.descriptor,
+        };
+        let child = &expr.child;
+        let recurrence = match op {
+            OperatorType::Neg => Recurrence::Neg(self.push_expr(&child[0], of)?),
+            OperatorType::Add => Recurrence::Add(
+                self.push_expr(&child[0], of)?,
+                self.push_expr(&child[1], of)?,
+            ),
+            OperatorType::Sub => Recurrence::Sub(
+                self.push_expr(&child[0], of)?,
+                self.push_expr(&child[1], of)?,
+            ),
+            OperatorType::Mul => Recurrence::Mul(
+                self.push_expr(&child[0], of)?,
+                self.push_expr(&child[1], of)?,
+            ),
+            OperatorType::Div => {
+                let (u, v) = (
+                    self.push_expr(&child[0], of)?,
+                    self.push_expr(&child[1], of)?,
+                );
+                if is_zero_expr(&self.nodes[v].coefficient[0]) {
+                    return Err(not_analytic(expr));
+                }
+                Recurrence::Div(u, v)
+            }
+            OperatorType::Exp => Recurrence::Exp(self.push_expr(&child[0], of)?),
+            OperatorType::Ln => {
+                let u = self.push_expr(&child[0], of)?;
+                if is_zero_expr(&self.nodes[u].coefficient[0]) {
+                    return Err(not_analytic(expr));
+                }
+                Recurrence::Ln(u)
+            }
+            OperatorType::Sin => Recurrence::Sin(self.push_expr(&child[0], of)?),
+            OperatorType::Cos => Recurrence::Cos(self.push_expr(&child[0], of)?),
+            OperatorType::Pow => return self.push_pow(expr, of),
+            _ => {
+                return Err(TaylorExpansionError {
+                    err_expr: expr.clone(),
+                    reason: "no coefficient recurrence",
+                })
+            }
+        };
+        Ok(self.push(recurrence))
+    }
+
+    /// u ^ n by repeated squaring for an integer n, which stays valid
+    /// where u vanishes, u ^ a with u(at) != 0 for another constant a and
+    /// exp(v * ln(u)) when the exponent depends on **of**.
+    fn push_pow(
+        &mut self,
+        expr: &Expression,
+        of: &Variable,
+    ) -> Result<usize, TaylorExpansionError<Expression>> {
+        let (base, exponent) = (&expr.child[0], &expr.child[1]);
+        if exponent.contains_variable(of) {
+            return self.push_expr(&exp(exponent.clone() * ln(base.clone())), of);
+        }
+        let u = self.push_expr(base, of)?;
+        let integer = match &exponent.root {
+            AstNode::Operand(AstOperand::Num(n)) => n.to_i64(),
+            _ => None,
+        };
+        match integer {
+            Some(n) => {
+                let mut result = self.push(Recurrence::Constant(Expression::one()));
+                let mut square = u;
+                let mut e = n.unsigned_abs();
+                while e > 0 {
+                    if e & 1 == 1 {
+                        result = self.push(Recurrence::Mul(result, square));
+                    }
+                    e >>= 1;
+                    if e > 0 {
+                        square = self.push(Recurrence::Mul(square, square));
+                    }
+                }
+                if n >= 0 {
+                    return Ok(result);
+                }
+                if is_zero_expr(&self.nodes[u].coefficient[0]) {
+                    return Err(not_analytic(expr));
+                }
+                let one = self.push(Recurrence::Constant(Expression::one()));
+                Ok(self.push(Recurrence::Div(one, result)))
+            }
+            None => {
+                if is_zero_expr(&self.nodes[u].coefficient[0]) {
+                    return Err(not_analytic(expr));
+                }
+                Ok(self.push(Recurrence::Power(u, exponent.clone())))
+            }
+        }
+    }
+
+    fn push(&mut self, recurrence: Recurrence) -> usize {
+        self.nodes.push(Node {
+            recurrence,
+            coefficient: vec![],
+            companion: vec![],
+        });
+        let idx = self.nodes.len() - 1;
+        self.extend(idx);
+        idx
+    }
+
+    /// k-th coefficient of the node **idx**, 0 past those computed.
+    fn get(&self, idx: usize, k: usize) -> Expression {
+        self.nodes[idx]
+            .coefficient
+            .get(k)
+            .cloned()
+            .unwrap_or(Expression::zero())
+    }
+
+    /// Sum of **weight**(j) * a_j * b_(k-j) for j in **from**..=k, skipping
+    /// the terms whose factors are known to vanish.
+    fn convolve(
+        a: &[Expression],
+        b: &[Expression],
+        k: usize,
+        from: usize,
+        weight: &dyn Fn(usize) -> Expression,
+    ) -> Expression {
+        (from..=k)
+            .filter(|j| !a[*j].is_zero() && !b[k - *j].is_zero())
+            .map(|j| weight(j) * a[j].clone() * b[k - j].clone())
+            .fold(Expression::zero(), |s, t| s + t)
+    }
+
+    /// Computes the next coefficient of the node **idx**, its children
+    /// are always at least as far along.
+    fn extend(&mut self, idx: usize) {
+        let k = self.nodes[idx].coefficient.len();
+        let kk = Expression::from(k as i64);
+        let unit = |_| Expression::one();
+        let index = |j: usize| Expression::from(j as i64);
+        let mut companion = None;
+        let c = match &self.nodes[idx].recurrence {
+            Recurrence::Constant(c) if k == 0 => c.clone(),
+            Recurrence::Constant(_) => Expression::zero(),
+            Recurrence::Identity => match k {
+                0 => self.at.clone(),
+                1 => Expression::one(),
+                _ => Expression::zero(),
+            },
+            Recurrence::Neg(u) => -self.get(*u, k),
+            Recurrence::Add(u, v) => self.get(*u, k) + self.get(*v, k),
+            Recurrence::Sub(u, v) => self.get(*u, k) - self.get(*v, k),
+            Recurrence::Mul(u, v) => {
+                let (u, v) = (&self.nodes[*u].coefficient, &self.nodes[*v].coefficient);
+                Self::convolve(u, v, k, 0, &unit)
+            }
+            Recurrence::Div(u, v) => {
+                // u = v w, so v_0 w_k = u_k - (v_1 w_(k-1) + ... + v_k w_0)
+                let w = &self.nodes[idx].coefficient;
+                let v = &self.nodes[*v].coefficient;
+                let s = if k == 0 {
+                    Expression::zero()
+                } else {
+                    Self::convolve(v, w, k, 1, &unit)
+                };
+                (self.get(*u, k) - s) / v[0].clone()
+            }
+            Recurrence::Exp(u) if k == 0 => exp(self.get(*u, 0)),
+            Recurrence::Exp(u) => {
+                // w' = u' w
+                let (u, w) = (&self.nodes[*u].coefficient, &self.nodes[idx].coefficient);
+                Self::convolve(u, w, k, 1, &index) / kk
+            }
+            Recurrence::Ln(u) if k == 0 => ln(self.get(*u, 0)),
+            Recurrence::Ln(u) => {
+                // u w' = u', so u_0 k w_k = k u_k - (sum of j w_j u_(k-j) for j < k)
+                let (u, w) = (&self.nodes[*u].coefficient, &self.nodes[idx].coefficient);
+                let s = (1..k)
+                    .filter(|j| !w[*j].is_zero() && !u[k - *j].is_zero())
+                    .map(|j| index(j) * w[j].clone() * u[k - j].clone())
+                    .fold(Expression::zero(), |s, t| s + t);
+                (u[k].clone() - s / kk) / u[0].clone()
+            }
+            Recurrence::Sin(u) | Recurrence::Cos(u) if k == 0 => {
+                let u0 = self.get(*u, 0);
+                let (s, c) = (sin(u0.clone()), cos(u0));
+                match &self.nodes[idx].recurrence {
+                    Recurrence::Sin(_) => {
+                        companion = Some(c);
+                        s
+                    }
+                    _ => {
+                        companion = Some(s);
+                        c
+                    }
+                }
+            }
+            Recurrence::Sin(u) | Recurrence::Cos(u) => {
+                // s' = u' c and c' = -u' s
+                let node = &self.nodes[idx];
+                let (s, c) = match node.recurrence {
+                    Recurrence::Sin(_) => (&node.coefficient, &node.companion),
+                    _ => (&node.companion, &node.coefficient),
+                };
+                let u = &self.nodes[*u].coefficient;
+                let sk = Self::convolve(u, c, k, 1, &index) / kk.clone();
+                let ck = -Self::convolve(u, s, k, 1, &index) / kk;
+                match node.recurrence {
+                    Recurrence::Sin(_) => {
+                        companion = Some(ck);
+                        sk
+                    }
+                    _ => {
+                        companion = Some(sk);
+                        ck
+                    }
+                }
+            }
+            Recurrence::Power(u, a) if k == 0 => self.get(*u, 0).pow(a.clone()),
+            Recurrence::Power(u, a) => {
+                // u w' = a u' w, so u_0 k w_k = sum of ((a + 1) j - k) u_j w_(k-j)
+                let (u, w) = (&self.nodes[*u].coefficient, &self.nodes[idx].coefficient);
+                let a1 = a.clone() + Expression::one();
+                let weight = |j: usize| a1.clone() * index(j) - Expression::from(k as i64);
+                Self::convolve(u, w, k, 1, &weight) / (kk * u[0].clone())
+            }
+        };
+        let node = &mut self.nodes[idx];
+        node.coefficient.push(c.num_aggregate());
+        if let Some(companion) = companion {
+            node.companion.push(companion.num_aggregate());
+        }
+    }
+}
+
+impl Iterator for TaylorCoefficients {
+    type Item = Expression;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let root = self.nodes.len() - 1;
+        // the constant coefficients were computed along with the nodes
+        if self.nodes[root].coefficient.len() <= self.next {
+            for idx in 0..self.nodes.len() {
+                self.extend(idx);
+            }
+        }
+        self.next += 1;
+        Some(self.nodes[root].coefficient[self.next - 1].clone())
+    }
+}
+
+#[cfg(test)]
+mod recurrence_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::taylor_expansion::partial_expansion::TaylorExpansion,
+        math_op::{cos::cos, exp::exp, ln::ln, pow::Pow, sin::sin},
+    };
+
+    use super::{has_recurrence, taylor_coefficients};
+
+    fn first(expr: &Expression, at: &Expression, n: usize) -> Vec<String> {
+        let vx = Variable::new_variable("x");
+        taylor_coefficients(expr, &vx, at)
+            .unwrap()
+            .take(n)
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn elementary_functions() {
+        let x = Expression::new_variable("x");
+        let zero = Expression::zero();
+        let sin_x = first(&sin(x.clone()), &zero, 8);
+        assert_eq!(sin_x, ["0", "1", "0", "-1/6", "0", "1/120", "0", "-1/5040"]);
+        let e = first(&exp(Expression::from(2) * x.clone()), &zero, 4);
+        assert_eq!(e, ["1", "2", "2", "4/3"]);
+        let l = first(&ln(x.clone()), &Expression::one(), 4);
+        assert_eq!(l, ["0", "1", "-1/2", "1/3"]);
+        let sqrt = first(
+            &(Expression::one() + x.clone()).pow(Expression::from(1) / Expression::from(2)),
+            &zero,
+            4,
+        );
+        assert_eq!(sqrt, ["1", "1/2", "-1/8", "1/16"]);
+        let q = first(&(sin(x.clone()) / cos(x.clone())), &zero, 6);
+        assert_eq!(q, ["0", "1", "0", "1/3", "0", "2/15"]);
+        let p = first(&(x.clone().pow(3) - x.clone()), &zero, 5);
+        assert_eq!(p, ["0", "-1", "0", "1", "0"]);
+    }
+
+    #[test]
+    fn high_order() {
+        // far out of reach of repeated differentiation
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let c = taylor_coefficients(&exp(x.clone()), &vx, &zero)
+            .unwrap()
+            .nth(20)
+            .unwrap();
+        assert_eq!(c.to_string(), "1/2432902008176640000");
+        let f = exp(sin(x.clone()));
+        let c: Vec<String> = taylor_coefficients(&f, &vx, &zero)
+            .unwrap()
+            .skip(4)
+            .take(5)
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(c, ["-1/8", "-1/15", "-1/240", "1/90", "31/5760"]);
+        assert!(taylor_coefficients(&ln(x.clone()), &vx, &Expression::zero()).is_err());
+        assert!(taylor_coefficients(&(Expression::one() / x), &vx, &Expression::zero()).is_err());
+    }
+
+    #[test]
+    fn residual() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let f = sin(x.clone());
+        assert!(has_recurrence(&f, &vx));
+        let s = f.taylor_expansion(&vx, &Expression::zero(), 5).unwrap();
+        assert!(s.residual.is_none());
+        assert_eq!(s.derive_residual().unwrap().to_string(), "-sinx");
+        // the residual follows the order
+        let s = s.truncate(2);
+        assert_eq!(s.derive_residual().unwrap().to_string(), "-cosx");
+    }
+}
@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{op::operand::Variable, tree::Expression},
+    compute::evaluate::interval::{eval_interval, Interval},
+};
+
+use super::partial_expansion::{PartialExpansion, TaylorExpansionError};
+
+/*
+ * Lagrange remainder: for x in an interval I around the point,
+ *   f(x) - T_n(x) = f^(n+1)(xi) / (n + 1)! (x - at)^(n+1)
+ * for some xi between at and x. Enclosing f^(n+1) over the hull of I and
+ * the point with interval arithmetic gives a bound that holds for every
+ * x in I, rounding included.
+*/
+
+type BoundResult<T> = Result<T, TaylorExpansionError<Expression>>;
+
+impl PartialExpansion {
+    /// Guaranteed bound on |f(of) - T(of)| for every value of **of** in
+    /// **over**, T being the truncated series and f the function it came
+    /// from. Only Taylor series expanded from a function have one.
+    pub fn remainder_bound(&self, over: &Interval) -> BoundResult<f64> {
+        let of = self.variable()?;
+        let residual = &self.derive_residual().ok_or(TaylorExpansionError {
+            err_expr: self.of.clone(),
+            reason: "an expansion without residual",
+        })?;
+        let at = eval_interval(&self.at, &HashMap::new()).map_err(|_| TaylorExpansionError {
+            err_expr: self.at.clone(),
+            reason: "a point that isn't a number",
+        })?;
+        let xi = over.hull(&at);
+        let derivative =
+            eval_interval(residual, &HashMap::from([(of.name, xi)])).map_err(|_| {
+                TaylorExpansionError {
+                    err_expr: residual.clone(),
+                    reason: "a residual that can't be bounded",
+                }
+            })?;
+        let n = self.order as i64 + 1;
+        let factorial = (1..=n).fold(Interval::point(1.0), |f, k| f * Interval::point(k as f64));
+        let h = Interval::point((*over - at).magnitude());
+        let bound = Interval::point(derivative.magnitude()) * h.powi(n) / factorial;
+        Ok(bound.hi)
+    }
+
+    fn variable(&self) -> BoundResult<Variable> {
+        Into::<Option<Variable>>::into(self.of.clone()).ok_or(TaylorExpansionError {
+            err_expr: self.of.clone(),
+            reason: "not a variable",
+        })
+    }
+}
+
+#[cfg(test)]
+mod remainder_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        compute::{
+            evaluate::interval::Interval, taylor_expansion::partial_expansion::TaylorExpansion,
+        },
+        math_op::{exp::exp, sin::sin},
+    };
+
+    #[test]
+    fn lagrange_bound() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let zero = Expression::zero();
+        let f = sin(x.clone());
+        let s = f.taylor_expansion(&vx, &zero, 5).unwrap();
+        let bound = s.remainder_bound(&Interval::new(-0.5, 0.5)).unwrap();
+        let t = 0.5 - 0.5_f64.powi(3) / 6.0 + 0.5_f64.powi(5) / 120.0;
+        assert!((0.5_f64.sin() - t).abs() <= bound);
+        assert!(bound < 3e-5);
+        // e - (1 + 1 + 1/2 + 1/6 + 1/24) <= e / 5!
+        let s = exp(x.clone()).taylor_expansion(&vx, &zero, 4).unwrap();
+        let bound = s.remainder_bound(&Interval::new(0.0, 1.0)).unwrap();
+        assert!(1_f64.exp() - 65.0 / 24.0 <= bound && bound < 0.0227);
+        // the sum of two series no longer knows its function
+        let s = (s.clone() + s).unwrap();
+        assert!(s.remainder_bound(&Interval::new(0.0, 1.0)).is_err());
+    }
+}
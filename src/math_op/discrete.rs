@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+use crate::ast::{
+    op::operator::{AstOperator, OperatorType},
+    tree::{AstNode, Expression},
+};
+
+fn gen_op_discrete(descriptor: OperatorType) -> AstOperator {
+    let symbol = match descriptor {
+        OperatorType::Factorial => "factorial",
+        OperatorType::Floor => "floor",
+        _ => unreachable!(),
+    };
+    AstOperator {
+        symbol: symbol.to_string(),
+        priority: 5_u32,
+        descriptor,
+    }
+}
+
+/// **n**!, only defined for non-negative integers.
+pub fn factorial<T: Into<Expression>>(n: T) -> Expression {
+    Expression {
+        root: AstNode::Operator(gen_op_discrete(OperatorType::Factorial)),
+        child: vec![n.into()],
+    }
+}
+
+/// Greatest integer not above **x**.
+pub fn floor<T: Into<Expression>>(x: T) -> Expression {
+    Expression {
+        root: AstNode::Operator(gen_op_discrete(OperatorType::Floor)),
+        child: vec![x.into()],
+    }
+}
+
+#[cfg(test)]
+mod discrete_tests {
+    use crate::ast::tree::Expression;
+
+    use super::{factorial, floor};
+
+    #[test]
+    fn string_fmt() {
+        let n = Expression::new_variable("n");
+        assert_eq!(
+            factorial(n.clone() + Expression::one()).to_string(),
+            "factorial(n + 1)"
+        );
+        assert_eq!(
+            (floor(n.clone() / Expression::from(2)) * n).to_string(),
+            "floor(n / 2) * n"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+
+use crate::ast::{
+    op::operator::{AstOperator, OperatorType},
+    tree::{AstNode, Expression},
+};
+
+fn gen_op_function(name: &str) -> AstOperator {
+    AstOperator {
+        symbol: name.to_string(),
+        priority: 5_u32,
+        descriptor: OperatorType::Function(name.to_string()),
+    }
+}
+
+/// Apply the undefined function **name** to **args**.
+pub fn function(name: &str, args: Vec<Expression>) -> Expression {
+    Expression {
+        root: AstNode::Operator(gen_op_function(name)),
+        child: args,
+    }
+}
+
+fn gen_op_function_derivative(name: &str, wrt: Vec<usize>, arity: usize) -> AstOperator {
+    // f''(x) when f is unary, f_12(x, y) otherwise
+    let symbol = if arity == 1 {
+        format!("{}{}", name, "'".repeat(wrt.len()))
+    } else {
+        let idx: Vec<String> = wrt.iter().map(|i| (i + 1).to_string()).collect();
+        format!("{}_{}", name, idx.join(""))
+    };
+    AstOperator {
+        symbol,
+        priority: 5_u32,
+        descriptor: OperatorType::FunctionDerivative(name.to_string(), wrt),
+    }
+}
+
+/// Partial derivative of the undefined function **name** in the arguments
+/// at the 0-based indices **wrt**, applied to **args**.
+pub fn function_derivative(name: &str, mut wrt: Vec<usize>, args: Vec<Expression>) -> Expression {
+    wrt.sort();
+    Expression {
+        root: AstNode::Operator(gen_op_function_derivative(name, wrt, args.len())),
+        child: args,
+    }
+}
+
+#[cfg(test)]
+mod function_tests {
+    use crate::ast::tree::Expression;
+
+    use super::{function, function_derivative};
+
+    #[test]
+    fn string_fmt() {
+        let x = Expression::new_variable("x");
+        let y = Expression::new_variable("y");
+        let f = function("f", vec![x.clone()]);
+        let g = function("g", vec![x.clone() + y.clone(), y]);
+        assert_eq!(f.to_string(), "f(x)");
+        assert_eq!(g.to_string(), "g(x + y, y)");
+        assert_eq!((f * x.clone()).to_string(), "f(x) * x");
+        let y = Expression::new_variable("y");
+        let d = function_derivative("f", vec![0, 0], vec![x.clone()]);
+        assert_eq!(d.to_string(), "f''(x)");
+        let d = function_derivative("g", vec![1, 0], vec![x, y]);
+        assert_eq!(d.to_string(), "g_12(x, y)");
+    }
+}
@@ -7,4 +7,6 @@ pub mod sin;
 pub mod cos;
 pub mod exp;
 pub mod ln;
-pub mod pow;
\ No newline at end of file
+pub mod pow;
+pub mod function;pub mod unevaluated;
+pub mod discrete;
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+
+use crate::{
+    ast::{
+        op::{
+            operand::Variable,
+            operator::{AstOperator, OperatorType},
+        },
+        tree::{AstNode, Expression},
+    },
+    compute::limit::Direction,
+};
+
+fn gen_op_unevaluated(descriptor: OperatorType) -> AstOperator {
+    let symbol = match descriptor {
+        OperatorType::Derivative => "Derivative",
+        OperatorType::Integral => "Integral",
+        OperatorType::Limit => "Limit",
+        OperatorType::Sum => "Sum",
+        _ => unreachable!(),
+    };
+    AstOperator {
+        symbol: symbol.to_string(),
+        priority: 5_u32,
+        descriptor,
+    }
+}
+
+fn unevaluated(descriptor: OperatorType, child: Vec<Expression>) -> Expression {
+    Expression {
+        root: AstNode::Operator(gen_op_unevaluated(descriptor)),
+        child,
+    }
+}
+
+/// d **body** / d **of**, kept as data until **doit()**.
+pub fn diff(body: Expression, of: &Variable) -> Expression {
+    unevaluated(
+        OperatorType::Derivative,
+        vec![body, Expression::from(of.clone())],
+    )
+}
+
+/// Indefinite integral of **body** in **of**.
+pub fn integral(body: Expression, of: &Variable) -> Expression {
+    unevaluated(
+        OperatorType::Integral,
+        vec![body, Expression::from(of.clone())],
+    )
+}
+
+/// Integral of **body** in **of** from **lower** to **upper**.
+pub fn definite_integral(
+    body: Expression,
+    of: &Variable,
+    lower: Expression,
+    upper: Expression,
+) -> Expression {
+    unevaluated(
+        OperatorType::Integral,
+        vec![body, Expression::from(of.clone()), lower, upper],
+    )
+}
+
+/// Limit of **body** as **of** approaches **to**.
+pub fn limit(body: Expression, of: &Variable, to: Expression) -> Expression {
+    unevaluated(
+        OperatorType::Limit,
+        vec![body, Expression::from(of.clone()), to],
+    )
+}
+
+/// One-sided limit, the side is stored as a trailing 1 (right) or -1 (left).
+pub fn one_sided_limit(
+    body: Expression,
+    of: &Variable,
+    to: Expression,
+    direction: Direction,
+) -> Expression {
+    let side = match direction {
+        Direction::Left => Expression::from(-1),
+        Direction::Right => Expression::from(1),
+        Direction::Both => return limit(body, of, to),
+    };
+    unevaluated(
+        OperatorType::Limit,
+        vec![body, Expression::from(of.clone()), to, side],
+    )
+}
+
+/// Sum of **body** for **of** running from **lower** to **upper** inclusive.
+pub fn sum(body: Expression, of: &Variable, lower: Expression, upper: Expression) -> Expression {
+    unevaluated(
+        OperatorType::Sum,
+        vec![body, Expression::from(of.clone()), lower, upper],
+    )
+}
+
+#[cfg(test)]
+mod unevaluated_tests {
+    use crate::{
+        ast::{op::operand::Variable, tree::Expression},
+        math_op::{pow::Pow, sin::sin},
+    };
+
+    use super::{definite_integral, diff, limit, sum};
+
+    #[test]
+    fn string_fmt() {
+        let x = Expression::new_variable("x");
+        let vx = Variable::new_variable("x");
+        let n = Variable::new_variable("n");
+        assert_eq!(diff(sin(x.clone()), &vx).to_string(), "Derivative(sinx, x)");
+        assert_eq!(
+            limit(sin(x.clone()) / x.clone(), &vx, Expression::zero()).to_string(),
+            "Limit(sinx / x, x, 0)"
+        );
+        let s = sum(
+            Expression::from(n.clone()).pow(2),
+            &n,
+            Expression::one(),
+            Expression::from(10),
+        );
+        assert_eq!(s.to_string(), "Sum(n ^ 2, n, 1, 10)");
+        let i = definite_integral(x.clone(), &vx, Expression::zero(), Expression::one()) * x;
+        assert_eq!(i.to_string(), "Integral(x, x, 0, 1) * x");
+    }
+}
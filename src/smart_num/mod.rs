@@ -3,6 +3,7 @@
 use std::{
     f64::consts::{E, PI},
     fmt::Display,
+    hash::Hash,
     ops::{Add, Div, Mul, Neg, Sub},
 };
 
@@ -96,6 +97,11 @@ impl SmartNum {
         self.tag == ConstType::E
     }
 
+    /// Integers and rationals, as opposed to reals and named constants.
+    pub fn is_exact(&self) -> bool {
+        !self.value.is_real() && !self.is_pi() && !self.is_e()
+    }
+
     pub fn sign(&self) -> i64 {
         match self.value {
             SmartNumVal::Integer(v) => v.signum(),
@@ -125,6 +131,46 @@ impl<'a> ToRational for &'a SmartNum {
     }
 }
 
+/// Exact numbers compare by value, so 2 equals 4/2. Reals compare
+/// bitwise together with their special constant tag.
+impl PartialEq for SmartNum {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_rational(), other.to_rational()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                self.tag == other.tag && self.to_f64().to_bits() == other.to_f64().to_bits()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SmartNum {}
+
+impl PartialEq<RationalNum> for SmartNum {
+    fn eq(&self, other: &RationalNum) -> bool {
+        self.to_rational().is_some_and(|v| v == *other)
+    }
+}
+
+impl Hash for SmartNum {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.to_rational() {
+            Some(v) => {
+                let v = v.reduce();
+                if v.nominator == 0 {
+                    0_u64.hash(state);
+                } else {
+                    v.sign.hash(state);
+                    v.nominator.hash(state);
+                    v.denominator.hash(state);
+                }
+            }
+            None => self.to_f64().to_bits().hash(state),
+        }
+    }
+}
+
 impl Display for SmartNum {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.tag != ConstType::Nothing {
@@ -165,6 +211,11 @@ where
 }
 
 fn wrap_val(value: SmartNumVal) -> SmartNum {
+    // Rationals like 4/2 are stored as integers.
+    let value = match value {
+        SmartNumVal::Rational(v) => SmartNumVal::from(v),
+        _ => value,
+    };
     if value.is_zero() {
         SmartNum::zero()
     } else if value.is_one() {
@@ -3,6 +3,8 @@ use std::{
     ops::{Add, Div, Mul, Neg, Sub},
 };
 
+use super::SmartNum;
+
 #[derive(Debug, Clone, Copy)]
 pub struct RationalNum {
     pub sign: i64,
@@ -41,6 +43,14 @@ fn gcd(a: u64, b: u64) -> u64 {
     }
 }
 
+fn gcd_wide(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd_wide(b, a % b)
+    }
+}
+
 impl RationalNum {
     pub fn reduce(&self) -> Self {
         let mut d = gcd(self.nominator, self.denominator);
@@ -77,6 +87,49 @@ impl RationalNum {
     pub fn to_f64(&self) -> f64 {
         (self.sign as f64) * (self.nominator as f64) / (self.denominator as f64)
     }
+
+    /// sign * nominator / denominator computed in 128 bits, None when the
+    /// reduced result doesn't fit.
+    fn from_wide(numerator: i128, denominator: u128) -> Option<RationalNum> {
+        let d = gcd_wide(numerator.unsigned_abs(), denominator).max(1);
+        Some(RationalNum {
+            sign: if numerator < 0 { -1 } else { 1 },
+            nominator: u64::try_from(numerator.unsigned_abs() / d).ok()?,
+            denominator: u64::try_from(denominator / d).ok()?,
+        })
+    }
+
+    fn wide_numerator(&self) -> i128 {
+        self.nominator as i128 * self.sign as i128
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<RationalNum> {
+        let a = self.wide_numerator().checked_mul(rhs.denominator as i128)?;
+        let b = rhs.wide_numerator().checked_mul(self.denominator as i128)?;
+        RationalNum::from_wide(
+            a.checked_add(b)?,
+            self.denominator as u128 * rhs.denominator as u128,
+        )
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<RationalNum> {
+        self.checked_add(-rhs)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<RationalNum> {
+        RationalNum::from_wide(
+            self.wide_numerator().checked_mul(rhs.wide_numerator())?,
+            self.denominator as u128 * rhs.denominator as u128,
+        )
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<RationalNum> {
+        RationalNum::from_wide(
+            self.wide_numerator()
+                .checked_mul(rhs.denominator as i128 * rhs.sign as i128)?,
+            self.denominator as u128 * rhs.nominator as u128,
+        )
+    }
 }
 
 impl Neg for RationalNum {
@@ -90,73 +143,36 @@ impl Neg for RationalNum {
     }
 }
 
+/// Exact while the result fits in 64 bits, a real number otherwise.
 impl Add for RationalNum {
-    type Output = RationalNum;
+    type Output = SmartNum;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let a = ((self.nominator * rhs.denominator) as i64) * self.sign;
-        let b = ((self.denominator * rhs.nominator) as i64) * rhs.sign;
-        let s = a + b;
-        let sign = s.signum();
-        let nominator = s.abs() as u64;
-        let denominator = self.denominator * rhs.denominator;
-        let res = RationalNum {
-            sign,
-            nominator,
-            denominator,
-        };
-        res.reduce()
+        SmartNum::from(self) + SmartNum::from(rhs)
     }
 }
 
 impl Sub for RationalNum {
-    type Output = RationalNum;
+    type Output = SmartNum;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let a = ((self.nominator * rhs.denominator) as i64) * self.sign;
-        let b = ((self.denominator * rhs.nominator) as i64) * rhs.sign;
-        let s = a - b;
-        let sign = s.signum();
-        let nominator = s.abs() as u64;
-        let denominator = self.denominator * rhs.denominator;
-        let res = RationalNum {
-            sign,
-            nominator,
-            denominator,
-        };
-        res.reduce()
+        SmartNum::from(self) - SmartNum::from(rhs)
     }
 }
 
 impl Mul for RationalNum {
-    type Output = RationalNum;
+    type Output = SmartNum;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let sign = self.sign * rhs.sign;
-        let nominator = self.nominator * rhs.nominator;
-        let denominator = self.denominator * rhs.denominator;
-        let res = RationalNum {
-            sign,
-            nominator,
-            denominator,
-        };
-        res.reduce()
+        SmartNum::from(self) * SmartNum::from(rhs)
     }
 }
 
 impl Div for RationalNum {
-    type Output = RationalNum;
+    type Output = SmartNum;
 
     fn div(self, rhs: Self) -> Self::Output {
-        let sign = self.sign * rhs.sign;
-        let nominator = self.nominator * rhs.denominator;
-        let denominator = self.denominator * rhs.nominator;
-        let res = RationalNum {
-            sign,
-            nominator,
-            denominator,
-        };
-        res.reduce()
+        SmartNum::from(self) / SmartNum::from(rhs)
     }
 }
 
@@ -174,7 +190,7 @@ impl From<i64> for RationalNum {
     fn from(v: i64) -> Self {
         RationalNum {
             sign: if v == 0 { 1_i64 } else { v.signum() },
-            nominator: v.abs() as u64,
+            nominator: v.unsigned_abs(),
             denominator: 1,
         }
     }
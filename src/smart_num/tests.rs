@@ -291,7 +291,7 @@ mod smart_num_tests {
 
 #[cfg(test)]
 mod rational_tests {
-    use super::super::rational::RationalNum;
+    use super::super::{rational::RationalNum, val_holder::IsClose, SmartNum};
 
     #[test]
     fn eq() {
@@ -437,6 +437,26 @@ mod rational_tests {
             assert_eq!(x / y, z);
         }
     }
+
+    #[test]
+    fn overflow() {
+        // the cross products go past i128
+        let x = RationalNum::new_positive(u64::MAX - 2, u64::MAX - 4).unwrap();
+        let y = RationalNum::new_positive(u64::MAX - 6, u64::MAX - 8).unwrap();
+        assert!(x.checked_add(y).is_none());
+        assert!(x.checked_sub(-y).is_none());
+        assert!((x + y).is_close(SmartNum::from(2.0), 1e-9));
+        let z = RationalNum::new_negative(u64::MAX, 2).unwrap();
+        assert!(z.checked_mul(z).is_none());
+        assert!(z.checked_div(x).is_none());
+        assert!((z * z).to_f64() > 8e37);
+        // whole numbers past i64 stay rational
+        let w = SmartNum::new_rational(1, (1 << 63) - 1, 2).unwrap() * SmartNum::from(4);
+        assert_eq!(w, RationalNum::from(u64::MAX - 1));
+        let w = RationalNum::new_negative(1 << 63, 1).unwrap();
+        assert_eq!(SmartNum::from(w), SmartNum::from(i64::MIN));
+        assert!(SmartNum::from(-w).to_f64() > 0.0);
+    }
 }
 
 #[cfg(test)]
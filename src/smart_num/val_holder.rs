@@ -155,6 +155,17 @@ impl From<f32> for SmartNumVal {
     }
 }
 
+impl From<RationalNum> for SmartNumVal {
+    fn from(v: RationalNum) -> Self {
+        let r = v.reduce();
+        // a whole number stays rational when it doesn't fit in an i64
+        match i64::try_from(r.sign as i128 * r.nominator as i128) {
+            Ok(n) if r.denominator == 1 => SmartNumVal::Integer(n),
+            _ => SmartNumVal::Rational(r),
+        }
+    }
+}
+
 // Automatically generated codes for other From<T>
 impl From<u8> for SmartNumVal {
     fn from(v: u8) -> Self {
@@ -198,19 +209,45 @@ impl From<u64> for SmartNumVal {
     }
 }
 
+/// Exact result of **op** on two rationals, or its floating point value
+/// when the numerator or denominator outgrows 64 bits.
+fn rational_or_real(
+    a: RationalNum,
+    b: RationalNum,
+    op: fn(RationalNum, RationalNum) -> Option<RationalNum>,
+    approx: fn(f64, f64) -> f64,
+) -> SmartNumVal {
+    op(a, b).map_or(
+        SmartNumVal::Real(approx(a.to_f64(), b.to_f64())),
+        SmartNumVal::Rational,
+    )
+}
+
 impl Add for SmartNumVal {
     type Output = SmartNumVal;
 
     fn add(self, rhs: Self) -> Self::Output {
         match self {
             SmartNumVal::Integer(i) => match rhs {
-                SmartNumVal::Integer(j) => SmartNumVal::Integer(i + j),
-                SmartNumVal::Rational(j) => SmartNumVal::Rational(RationalNum::from(i) + j),
+                SmartNumVal::Integer(j) => i
+                    .checked_add(j)
+                    .map_or(SmartNumVal::Real(i as f64 + j as f64), SmartNumVal::Integer),
+                SmartNumVal::Rational(j) => {
+                    rational_or_real(RationalNum::from(i), j, RationalNum::checked_add, |a, b| {
+                        a + b
+                    })
+                }
                 SmartNumVal::Real(j) => SmartNumVal::Real((i as f64) + j),
             },
             SmartNumVal::Rational(i) => match rhs {
-                SmartNumVal::Integer(j) => SmartNumVal::Rational(i + RationalNum::from(j)),
-                SmartNumVal::Rational(j) => SmartNumVal::Rational(i + j),
+                SmartNumVal::Integer(j) => {
+                    rational_or_real(i, RationalNum::from(j), RationalNum::checked_add, |a, b| {
+                        a + b
+                    })
+                }
+                SmartNumVal::Rational(j) => {
+                    rational_or_real(i, j, RationalNum::checked_add, |a, b| a + b)
+                }
                 SmartNumVal::Real(j) => SmartNumVal::Real(i.to_f64() + j),
             },
             SmartNumVal::Real(i) => match rhs {
@@ -228,13 +265,25 @@ impl Sub for SmartNumVal {
     fn sub(self, rhs: Self) -> Self::Output {
         match self {
             SmartNumVal::Integer(i) => match rhs {
-                SmartNumVal::Integer(j) => SmartNumVal::Integer(i - j),
-                SmartNumVal::Rational(j) => SmartNumVal::Rational(RationalNum::from(i) - j),
+                SmartNumVal::Integer(j) => i
+                    .checked_sub(j)
+                    .map_or(SmartNumVal::Real(i as f64 - j as f64), SmartNumVal::Integer),
+                SmartNumVal::Rational(j) => {
+                    rational_or_real(RationalNum::from(i), j, RationalNum::checked_sub, |a, b| {
+                        a - b
+                    })
+                }
                 SmartNumVal::Real(j) => SmartNumVal::Real((i as f64) - j),
             },
             SmartNumVal::Rational(i) => match rhs {
-                SmartNumVal::Integer(j) => SmartNumVal::Rational(i - RationalNum::from(j)),
-                SmartNumVal::Rational(j) => SmartNumVal::Rational(i - j),
+                SmartNumVal::Integer(j) => {
+                    rational_or_real(i, RationalNum::from(j), RationalNum::checked_sub, |a, b| {
+                        a - b
+                    })
+                }
+                SmartNumVal::Rational(j) => {
+                    rational_or_real(i, j, RationalNum::checked_sub, |a, b| a - b)
+                }
                 SmartNumVal::Real(j) => SmartNumVal::Real(i.to_f64() - j),
             },
             SmartNumVal::Real(i) => match rhs {
@@ -255,13 +304,27 @@ impl Mul for SmartNumVal {
         } else {
             match self {
                 SmartNumVal::Integer(i) => match rhs {
-                    SmartNumVal::Integer(j) => SmartNumVal::Integer(i * j),
-                    SmartNumVal::Rational(j) => SmartNumVal::Rational(RationalNum::from(i) * j),
+                    SmartNumVal::Integer(j) => i
+                        .checked_mul(j)
+                        .map_or(SmartNumVal::Real(i as f64 * j as f64), SmartNumVal::Integer),
+                    SmartNumVal::Rational(j) => rational_or_real(
+                        RationalNum::from(i),
+                        j,
+                        RationalNum::checked_mul,
+                        |a, b| a * b,
+                    ),
                     SmartNumVal::Real(j) => SmartNumVal::Real((i as f64) * j),
                 },
                 SmartNumVal::Rational(i) => match rhs {
-                    SmartNumVal::Integer(j) => SmartNumVal::Rational(i * RationalNum::from(j)),
-                    SmartNumVal::Rational(j) => SmartNumVal::Rational(i * j),
+                    SmartNumVal::Integer(j) => rational_or_real(
+                        i,
+                        RationalNum::from(j),
+                        RationalNum::checked_mul,
+                        |a, b| a * b,
+                    ),
+                    SmartNumVal::Rational(j) => {
+                        rational_or_real(i, j, RationalNum::checked_mul, |a, b| a * b)
+                    }
                     SmartNumVal::Real(j) => SmartNumVal::Real(i.to_f64() * j),
                 },
                 SmartNumVal::Real(i) => match rhs {
@@ -283,15 +346,30 @@ impl Div for SmartNumVal {
         } else {
             match self {
                 SmartNumVal::Integer(i) => match rhs {
-                    SmartNumVal::Integer(j) => {
-                        SmartNumVal::Rational(RationalNum::from(i) / RationalNum::from(j))
-                    }
-                    SmartNumVal::Rational(j) => SmartNumVal::Rational(RationalNum::from(i) / j),
+                    SmartNumVal::Integer(j) => rational_or_real(
+                        RationalNum::from(i),
+                        RationalNum::from(j),
+                        RationalNum::checked_div,
+                        |a, b| a / b,
+                    ),
+                    SmartNumVal::Rational(j) => rational_or_real(
+                        RationalNum::from(i),
+                        j,
+                        RationalNum::checked_div,
+                        |a, b| a / b,
+                    ),
                     SmartNumVal::Real(j) => SmartNumVal::Real((i as f64) / j),
                 },
                 SmartNumVal::Rational(i) => match rhs {
-                    SmartNumVal::Integer(j) => SmartNumVal::Rational(i / RationalNum::from(j)),
-                    SmartNumVal::Rational(j) => SmartNumVal::Rational(i / j),
+                    SmartNumVal::Integer(j) => rational_or_real(
+                        i,
+                        RationalNum::from(j),
+                        RationalNum::checked_div,
+                        |a, b| a / b,
+                    ),
+                    SmartNumVal::Rational(j) => {
+                        rational_or_real(i, j, RationalNum::checked_div, |a, b| a / b)
+                    }
                     SmartNumVal::Real(j) => SmartNumVal::Real(i.to_f64() / j),
                 },
                 SmartNumVal::Real(i) => match rhs {